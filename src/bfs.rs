@@ -0,0 +1,34 @@
+use crate::prelude::*;
+
+/// Runs a level-synchronous breadth-first visit starting from `start`, returning the number of levels.
+///
+/// # Implementation details
+/// The elements of each level are expanded in parallel, and `expand` is
+/// expected to push the elements of the next level onto the provided frontier.
+/// Once a level is exhausted, the current and next frontiers are swapped
+/// and the new next frontier is cleared, retaining its capacity, so only
+/// two frontiers are ever allocated. The visit stops when a level is empty.
+/// Keeping track of the already visited elements is up to `expand`, e.g.
+/// with an atomic flag per node.
+///
+/// # Arguments
+/// * `start`: T - Element of the first level.
+/// * `expand`: F - Closure pushing the successors of an element onto the next frontier.
+pub fn bfs<T, F>(start: T, expand: F) -> usize
+where
+    T: Send + Sync,
+    F: Fn(&T, &Frontier<T>) + Sync + Send,
+{
+    let mut current = Frontier::new();
+    let mut next = Frontier::new();
+    current.push(start);
+
+    let mut levels = 0;
+    while !current.is_empty() {
+        levels += 1;
+        current.par_iter().for_each(|value| expand(value, &next));
+        core::mem::swap(&mut current, &mut next);
+        next.clear();
+    }
+    levels
+}
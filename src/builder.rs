@@ -0,0 +1,105 @@
+use crate::prelude::*;
+use core::marker::PhantomData;
+use rayon::ThreadPool;
+
+#[derive(Debug, Clone)]
+/// Builder of [`Frontier`] objects, combining the options of its constructors.
+///
+/// # Implementation details
+/// The number of sub-vectors of the built frontier is, in order of precedence:
+/// * the number explicitly provided with [`FrontierBuilder::num_shards`],
+///   or with its alias [`FrontierBuilder::shards`];
+/// * the number of threads of the pool provided with [`FrontierBuilder::threads`];
+/// * the system number of threads, as in [`Frontier::new`].
+///
+/// Do note that when both a pool and an explicit number of sub-vectors are
+/// provided, the number of sub-vectors should not be smaller than the number
+/// of threads of the pool, as each thread pushes onto the sub-vector with its own index.
+pub struct FrontierBuilder<'a, T> {
+    threads: Option<&'a ThreadPool>,
+    shards: Option<usize>,
+    capacity: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T> Default for FrontierBuilder<'a, T> {
+    fn default() -> Self {
+        FrontierBuilder {
+            threads: None,
+            shards: None,
+            capacity: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> FrontierBuilder<'a, T> {
+    #[inline]
+    /// Create new builder with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    /// Sets the [`ThreadPool`] the frontier is used from.
+    pub fn threads(mut self, thread_pool: &'a ThreadPool) -> Self {
+        self.threads = Some(thread_pool);
+        self
+    }
+
+    #[inline]
+    /// Sets the number of sub-vectors, overriding the number of threads of the pool.
+    ///
+    /// # Panics
+    /// If `num_shards` is zero.
+    pub fn num_shards(mut self, num_shards: usize) -> Self {
+        assert!(
+            num_shards > 0,
+            "A frontier requires at least one sub-vector."
+        );
+        self.shards = Some(num_shards);
+        self
+    }
+
+    #[inline]
+    /// Sets the number of sub-vectors, as [`FrontierBuilder::num_shards`].
+    ///
+    /// # Panics
+    /// If `shards` is zero.
+    pub fn shards(self, shards: usize) -> Self {
+        self.num_shards(shards)
+    }
+
+    #[inline]
+    /// Sets the total capacity, distributed as in [`Frontier::with_capacity`].
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    #[inline]
+    /// Create the frontier with the provided options.
+    pub fn build(self) -> Frontier<'a, T> {
+        let n_threads = self
+            .shards
+            .or_else(|| {
+                self.threads
+                    .map(|thread_pool| thread_pool.current_num_threads().max(1))
+            })
+            .unwrap_or_else(Frontier::<T>::system_number_of_threads);
+        let mut frontier = Frontier::from_shards(Frontier::<T>::shards_with_capacity(
+            n_threads,
+            self.capacity,
+        ));
+        frontier.threads = self.threads;
+        frontier
+    }
+}
+
+impl<'a, T> Frontier<'a, T> {
+    #[inline]
+    /// Returns a builder to configure the number of sub-vectors, the capacity and the pool together.
+    pub fn builder() -> FrontierBuilder<'a, T> {
+        FrontierBuilder::new()
+    }
+}
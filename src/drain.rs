@@ -0,0 +1,67 @@
+use std::iter::FlatMap;
+use std::slice::IterMut;
+use std::vec::Drain;
+
+type DrainShard<'b, T> = fn(&'b mut Vec<T>) -> Drain<'b, T>;
+
+/// Iterator moving the elements out of a frontier, see [`Frontier::drain`](crate::prelude::Frontier::drain).
+///
+/// The sub-vectors are drained lazily from both ends: when the front and
+/// the back cursors meet in the same sub-vector, they share its drain.
+/// On drop, all the elements not yet yielded are dropped, so the frontier
+/// is left empty regardless of which end was consumed.
+pub struct FrontierDrain<'b, T> {
+    iter: FlatMap<IterMut<'b, Vec<T>>, Drain<'b, T>, DrainShard<'b, T>>,
+    len: usize,
+}
+
+impl<'b, T> FrontierDrain<'b, T> {
+    pub(crate) fn new(shards: &'b mut [Vec<T>]) -> Self {
+        let len = shards.iter().map(|vector| vector.len()).sum();
+        FrontierDrain {
+            iter: shards
+                .iter_mut()
+                .flat_map((|vector| vector.drain(..)) as DrainShard<'b, T>),
+            len,
+        }
+    }
+}
+
+impl<'b, T> core::fmt::Debug for FrontierDrain<'b, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrontierDrain")
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<'b, T> Iterator for FrontierDrain<'b, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let value = self.iter.next()?;
+        self.len -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'b, T> DoubleEndedIterator for FrontierDrain<'b, T> {
+    fn next_back(&mut self) -> Option<T> {
+        let value = self.iter.next_back()?;
+        self.len -= 1;
+        Some(value)
+    }
+}
+
+impl<'b, T> ExactSizeIterator for FrontierDrain<'b, T> {}
+
+impl<'b, T> Drop for FrontierDrain<'b, T> {
+    fn drop(&mut self) {
+        // the sub-vectors not reached yet by either cursor still hold their elements
+        self.iter.by_ref().for_each(drop);
+    }
+}
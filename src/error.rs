@@ -0,0 +1,18 @@
+use core::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Errors returned by the checked constructors of a frontier, see [`Frontier::try_from_shards`](crate::prelude::Frontier::try_from_shards).
+pub enum FrontierError {
+    /// No sub-vectors were provided, while a frontier requires at least one.
+    NoShards,
+}
+
+impl Display for FrontierError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FrontierError::NoShards => write!(f, "a frontier requires at least one sub-vector"),
+        }
+    }
+}
+
+impl std::error::Error for FrontierError {}
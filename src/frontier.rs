@@ -1,12 +1,93 @@
+use std::cell::RefCell;
 use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::prelude::*;
+use crate::seen_set::SeenSet;
+use crate::shards::Shards;
 use rayon::{prelude::*, ThreadPool};
 
-#[derive(Debug, Clone)]
+/// Closure resolving the index of the sub-vector the calling thread should use.
+type IndexFn = Arc<dyn Fn() -> usize + Send + Sync + 'static>;
+
+/// Unordered vector supporting concurrent pushes through a shared reference.
+///
+/// # Thread safety
+/// The elements are split in one sub-vector per thread, and each thread only
+/// ever pushes to (or pops from) the sub-vector at its own index, so no two
+/// threads mutate the same sub-vector at the same time and a `&Frontier` can
+/// be shared across the workers of a [`ThreadPool`].
+/// This requires the frontier to be used from the pool it was created for
+/// (or from the global one), as threads of a different pool may share an index.
+#[derive(Clone)]
 pub struct Frontier<'a, T> {
-    data: Vec<Vec<T>>,
-    threads: Option<&'a ThreadPool>,
+    data: Shards<Vec<T>>,
+    pub(crate) threads: Option<&'a ThreadPool>,
+    index_fn: Option<IndexFn>,
+    shard_capacity: Option<usize>,
+    seen: SeenSet<T>,
+    // lengths of the frontier recorded by `mark_level`
+    level_boundaries: Vec<usize>,
+    // sub-vector receiving the next element of `push_balanced`
+    next_balanced_shard: usize,
+}
+
+/// Maximum number of elements shown by the `Display` implementation of [`Frontier`].
+const DISPLAY_PREVIEW_LEN: usize = 8;
+
+// A frontier is only useful if it can be shared across rayon workers.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Frontier<u32>>();
+};
+
+/// Prints the number of sub-vectors, the total length and the size of each sub-vector.
+///
+/// The elements themselves are only printed with the alternate flag, i.e.
+/// `{:#?}`, as dumping a large frontier is rarely what is wanted in logs.
+impl<'a, T> core::fmt::Debug for Frontier<'a, T>
+where
+    T: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let verbose = f.alternate();
+        let mut debug = f.debug_struct("Frontier");
+        debug
+            .field("shards", &self.number_of_threads())
+            .field("len", &self.len())
+            .field("sizes", &self.vector_sizes());
+        if verbose {
+            debug
+                .field("data", &self.data)
+                .field("threads", &self.threads)
+                .field("index_fn", &self.index_fn.is_some())
+                .field("shard_capacity", &self.shard_capacity);
+        }
+        debug.finish()
+    }
+}
+
+/// Prints at most the first few elements in the order of [`Frontier::iter`],
+/// followed by an ellipsis when the frontier is longer, e.g. `[1, 2, 3, ...]`.
+impl<'a, T> core::fmt::Display for Frontier<'a, T>
+where
+    T: core::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for (i, value) in self.iter().take(DISPLAY_PREVIEW_LEN).enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        if self.len() > DISPLAY_PREVIEW_LEN {
+            write!(f, ", ...")?;
+        }
+        write!(f, "]")
+    }
 }
 
 impl<'a, T> AsRef<[Vec<T>]> for Frontier<'a, T> {
@@ -25,11 +106,105 @@ impl<'a, T> PartialEq for Frontier<'a, T>
 where
     T: PartialEq,
 {
+    /// Returns whether the two frontiers have the same elements in the same [`Frontier::iter`] order.
+    ///
+    /// # Implementation details
+    /// The comparison is order-sensitive but does not depend on the sub-vector
+    /// boundaries: two frontiers with the same multiset of elements distributed
+    /// differently across the sub-vectors are usually NOT equal, in which case
+    /// [`Frontier::unordered_eq`] can be used instead.
     fn eq(&self, other: &Self) -> bool {
         self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a.eq(b))
     }
 }
 
+impl<'a, T> Eq for Frontier<'a, T> where T: Eq {}
+
+impl<'a, T> Hash for Frontier<'a, T>
+where
+    T: Hash,
+{
+    /// Hashes the elements in the [`Frontier::iter`] order, consistently with `PartialEq`.
+    ///
+    /// # Implementation details
+    /// The hash depends on the layout of the sub-vectors only through the
+    /// iteration order, and not on the raw sub-vector boundaries: frontiers
+    /// that compare equal hash equally even when distributed differently.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        self.iter().for_each(|value| value.hash(state));
+    }
+}
+
+#[inline(always)]
+/// Returns the index of the sub-vector the calling thread should use.
+///
+/// # Panics
+/// If called from a thread pool different from the provided one.
+pub(crate) fn current_thread_index(threads: Option<&ThreadPool>) -> usize {
+    if let Some(thread_pool) = threads {
+        // We are using a custom ThreadPool so we want the call to come
+        // from the same ThreadPool or from the main thread.
+        if let Some(index) = thread_pool.current_thread_index() {
+            // The call is from the custom ThreadPool
+            index
+        } else {
+            // The call is from outside the custom ThreadPool so we want
+            // it to originate from no pool at all
+            if rayon::current_thread_index().is_some() {
+                panic!("Parallel frontier called from external thread pool")
+            } else {
+                0
+            }
+        }
+    } else {
+        // We are not using a custom ThreadPool so the global one is used
+        rayon::current_thread_index().unwrap_or(0)
+    }
+}
+
+#[inline]
+/// Executes the provided operation within the provided [`ThreadPool`], if any.
+pub(crate) fn install_in<OP, R>(threads: Option<&ThreadPool>, op: OP) -> R
+where
+    OP: FnOnce() -> R + Send,
+    R: Send,
+{
+    if let Some(thread_pool) = threads {
+        thread_pool.install(op)
+    } else {
+        op()
+    }
+}
+
+#[inline]
+/// Returns the share of the capacity of the given sub-vector, spreading the
+/// remainder one slot each over the first sub-vectors.
+fn capacity_share(capacity: usize, n_threads: usize, thread_id: usize) -> usize {
+    capacity / n_threads + usize::from(thread_id < capacity % n_threads)
+}
+
+impl<'a, T> core::ops::Index<usize> for Frontier<'a, T> {
+    type Output = T;
+
+    /// Returns the element at the provided global index, see [`Frontier::get`].
+    ///
+    /// As for `get`, no thread may push while the returned reference is alive.
+    ///
+    /// # Panics
+    /// If the index is out of bounds.
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            ),
+        }
+    }
+}
+
 impl<'a, T> TryFrom<Vec<Vec<T>>> for Frontier<'a, T> {
     type Error = String;
 
@@ -46,10 +221,7 @@ impl<'a, T> TryFrom<Vec<Vec<T>>> for Frontier<'a, T> {
                 Frontier::<T>::system_number_of_threads()
             ));
         }
-        Ok(Self {
-            data: value,
-            threads: None,
-        })
+        Ok(Self::from_shards(value))
     }
 }
 
@@ -64,7 +236,7 @@ impl<'a, T> From<Vec<T>> for Frontier<'a, T> {
 
 impl<'a, T> From<Frontier<'a, T>> for Vec<Vec<T>> {
     fn from(val: Frontier<'a, T>) -> Self {
-        val.data
+        val.data.into_vec()
     }
 }
 
@@ -77,6 +249,33 @@ where
     }
 }
 
+/// Each rayon worker pushes to its own sub-vector, as with [`Frontier::push`].
+///
+/// When the length of the parallel iterator is known, each sub-vector
+/// reserves its share of it upfront.
+impl<'a, T> ParallelExtend<T> for Frontier<'a, T>
+where
+    T: Send + Sync,
+{
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = T>,
+    {
+        let par_iter = par_iter.into_par_iter();
+        if let Some(len) = par_iter.opt_len() {
+            let n_threads = self.number_of_threads();
+            self.data
+                .iter_mut()
+                .enumerate()
+                .for_each(|(thread_id, vector)| {
+                    vector.reserve(capacity_share(len, n_threads, thread_id))
+                });
+        }
+        let frontier = &*self;
+        frontier.install(|| par_iter.for_each(|value| frontier.push(value)));
+    }
+}
+
 impl<'a, T> core::default::Default for Frontier<'a, T> {
     /// Create default frontier object with `system_number_of_threads` empty sub-vectors.
     fn default() -> Self {
@@ -90,67 +289,291 @@ impl<'a, T: Clone> Frontier<'a, T> {
     pub fn concat(&self) -> Vec<T> {
         self.data.concat()
     }
+
+    #[inline]
+    /// Returns a sorted vector with the elements of the frontier.
+    ///
+    /// # Implementation details
+    /// Differently from `concat`, the order of the result does not depend on
+    /// how the elements are distributed across the sub-vectors.
+    pub fn to_sorted_vec(&self) -> Vec<T>
+    where
+        T: Ord,
+    {
+        let mut values = self.concat();
+        values.sort();
+        values
+    }
+
+    #[inline]
+    /// Returns whether the two frontiers contain the same multiset of elements, regardless of their order.
+    ///
+    /// # Implementation details
+    /// Differently from `PartialEq`, the comparison does not depend on how
+    /// the elements are distributed across the sub-vectors, at the cost of
+    /// sorting a copy of the elements of both frontiers.
+    pub fn unordered_eq(&self, other: &Self) -> bool
+    where
+        T: Ord,
+    {
+        self.len() == other.len() && self.to_sorted_vec() == other.to_sorted_vec()
+    }
+
+    #[inline]
+    /// Writes the elements of the frontier into the provided vector, replacing its content.
+    ///
+    /// # Implementation details
+    /// The vector is cleared and then extended with the sub-vectors in order,
+    /// so its capacity is reused and never shrunk, and it is reallocated
+    /// at most once when it cannot hold all the elements of the frontier.
+    ///
+    /// # Arguments
+    /// * `out`: `&mut Vec<T>` - Vector where to write the elements.
+    pub fn concat_into(&self, out: &mut Vec<T>) {
+        out.clear();
+        out.reserve(self.len());
+        self.data.iter().for_each(|v| out.extend_from_slice(v));
+    }
 }
 
 impl<'a, T> Frontier<'a, T> {
     #[inline]
     /// Create new frontier object with `system_number_of_threads` empty sub-vectors.
     pub fn new() -> Self {
-        let n_threads = Frontier::<T>::system_number_of_threads();
-        Frontier {
-            data: (0..n_threads).map(|_| Vec::new()).collect::<Vec<_>>(),
-            threads: None,
-        }
+        Frontier::builder().build()
     }
+
     #[inline]
     /// Create new frontier object with `system_number_of_threads` empty sub-vectors.
     ///
     /// # Implementation details
     /// Do note that the provided capacity is distributed roughly uniformely
-    /// across the `system_number_of_threads` subvectors.
+    /// across the `system_number_of_threads` subvectors: the remainder of
+    /// the division is spread one slot each over the first subvectors, so that
+    /// the total reserved capacity is at least `capacity`.
     pub fn with_capacity(capacity: usize) -> Self {
+        Frontier::builder().capacity(capacity).build()
+    }
+
+    #[inline]
+    /// Create new frontier object for the specified [`ThreadPool`].
+    ///
+    /// # Implementation details
+    /// The optional capacity is distributed across the subvectors as in [`Frontier::with_capacity`].
+    pub fn with_threads(thread_pool: &'a ThreadPool, capacity: Option<usize>) -> Self {
+        Frontier::builder()
+            .threads(thread_pool)
+            .capacity(capacity.unwrap_or(0))
+            .build()
+    }
+
+    #[inline]
+    /// Create new frontier object with `n_shards` empty sub-vectors, using
+    /// the provided closure to resolve the sub-vector of the calling thread.
+    ///
+    /// # Implementation details
+    /// This allows to use the frontier from threading models other than Rayon:
+    /// the closure is called by `push` and `pop` in place of the Rayon thread
+    /// index lookup and must return, for each thread, a stable index in
+    /// `0..n_shards` that is not shared with any other thread.
+    ///
+    /// # Arguments
+    /// * `n_shards`: usize - Number of sub-vectors of the frontier.
+    /// * `index_fn`: F - Closure returning the index of the current thread.
+    ///
+    /// # Panics
+    /// If `n_shards` is zero.
+    pub fn with_index_fn<F>(n_shards: usize, index_fn: F) -> Self
+    where
+        F: Fn() -> usize + Send + Sync + 'static,
+    {
+        assert!(n_shards > 0, "A frontier requires at least one sub-vector.");
+        Frontier {
+            index_fn: Some(Arc::new(index_fn)),
+            ..Frontier::from_shards((0..n_shards).map(|_| Vec::new()).collect::<Vec<_>>())
+        }
+    }
+
+    #[inline]
+    /// Create new frontier object with `n_shards` empty sub-vectors, independently of the number of threads.
+    ///
+    /// # Implementation details
+    /// Outside of a Rayon thread pool the elements are pushed onto the first
+    /// sub-vector as usual, while inside a pool each thread pushes onto the
+    /// sub-vector at its own index, so that no two threads ever share one.
+    /// This is meant for oversubscription and for deterministic tests, as
+    /// the number of sub-vectors does not depend on the machine, but
+    /// `n_shards` must be at least the number of threads of the pools the
+    /// frontier is pushed from: pushing from a thread whose index is not
+    /// smaller than `n_shards` panics.
+    ///
+    /// # Arguments
+    /// * `n_shards`: usize - Number of sub-vectors of the frontier.
+    ///
+    /// # Panics
+    /// If `n_shards` is zero.
+    pub fn with_num_shards(n_shards: usize) -> Self {
+        Frontier::builder().num_shards(n_shards).build()
+    }
+
+    #[inline]
+    /// Create new bounded frontier object with `system_number_of_threads` sub-vectors.
+    ///
+    /// # Implementation details
+    /// The cap is per sub-vector and not global: each sub-vector is allocated
+    /// with exactly `per_shard` capacity and [`Frontier::try_push`] refuses
+    /// the elements that would make the current thread sub-vector exceed it,
+    /// so the frontier never reallocates while it is being filled through
+    /// `try_push`. The `push` method keeps its unbounded behaviour.
+    ///
+    /// # Arguments
+    /// * `per_shard`: usize - Maximum number of elements of each sub-vector.
+    pub fn with_bounded_capacity(per_shard: usize) -> Self {
         let n_threads = Frontier::<T>::system_number_of_threads();
         Frontier {
-            data: (0..n_threads)
-                .map(|_| Vec::with_capacity(capacity / n_threads))
-                .collect::<Vec<_>>(),
+            shard_capacity: Some(per_shard),
+            ..Frontier::from_shards(
+                (0..n_threads)
+                    .map(|_| Vec::with_capacity(per_shard))
+                    .collect::<Vec<_>>(),
+            )
+        }
+    }
+
+    #[inline]
+    /// Returns `n_threads` empty sub-vectors whose capacities sum to `capacity`.
+    pub(crate) fn shards_with_capacity(n_threads: usize, capacity: usize) -> Vec<Vec<T>> {
+        (0..n_threads)
+            .map(|thread_id| Vec::with_capacity(capacity_share(capacity, n_threads, thread_id)))
+            .collect::<Vec<_>>()
+    }
+
+    #[inline]
+    /// Create new frontier object owning the provided sub-vectors.
+    ///
+    /// # Implementation details
+    /// The number of sub-vectors is not validated: most methods assume
+    /// there is at least one of them, so prefer [`Frontier::try_from_shards`]
+    /// when `data` may be empty.
+    ///
+    /// # Arguments
+    /// * `data`: `Vec<Vec<T>>` - The sub-vectors, one per thread.
+    pub fn from_shards(data: Vec<Vec<T>>) -> Self {
+        Frontier {
+            data: Shards::from(data),
             threads: None,
+            index_fn: None,
+            shard_capacity: None,
+            seen: SeenSet::new(),
+            level_boundaries: Vec::new(),
+            next_balanced_shard: 0,
         }
     }
 
     #[inline]
-    /// Create new frontier object for the specified [`ThreadPool`].
-    pub fn with_threads(thread_pool: &'a ThreadPool, capacity: Option<usize>) -> Self {
-        let n_threads = thread_pool.current_num_threads();
+    /// Create new frontier object owning the provided sub-vectors, checking there is at least one of them.
+    ///
+    /// # Arguments
+    /// * `data`: `Vec<Vec<T>>` - The sub-vectors, one per thread.
+    pub fn try_from_shards(data: Vec<Vec<T>>) -> Result<Self, FrontierError> {
+        if data.is_empty() {
+            return Err(FrontierError::NoShards);
+        }
+        Ok(Self::from_shards(data))
+    }
+
+    #[inline]
+    /// Create new empty frontier with the same configuration of this one.
+    ///
+    /// # Implementation details
+    /// The new frontier has the same number of sub-vectors, each allocated
+    /// with the capacity of the corresponding sub-vector of this frontier,
+    /// and shares the same [`ThreadPool`], thread index resolution and per
+    /// sub-vector cap, but none of the elements.
+    /// This is handy to create the next frontier of a level-synchronous traversal.
+    pub fn clone_empty(&self) -> Self {
         Frontier {
-            data: (0..n_threads)
-                .map(|_| Vec::with_capacity(capacity.unwrap_or(0) / n_threads))
-                .collect::<Vec<_>>(),
-            threads: Some(thread_pool),
+            threads: self.threads,
+            index_fn: self.index_fn.clone(),
+            shard_capacity: self.shard_capacity,
+            ..Frontier::from_shards(
+                self.data
+                    .iter()
+                    .map(|v| Vec::with_capacity(v.capacity()))
+                    .collect::<Vec<_>>(),
+            )
+        }
+    }
+
+    #[inline]
+    /// Returns the sub-vector and the offset within it of the element at the provided global index.
+    fn locate(&self, index: usize) -> Option<(usize, usize)> {
+        if index >= self.len() {
+            return None;
         }
+        let cumulative_lens = self.cumulative_lens();
+        // the last sub-vector starting at or before the index, which is not
+        // empty since the index is in range
+        let thread_id = cumulative_lens.partition_point(|&start| start <= index) - 1;
+        Some((thread_id, index - cumulative_lens[thread_id]))
+    }
+
+    #[inline]
+    /// Returns the index of the sub-vector holding the element at the provided global index, and the offset within it.
+    ///
+    /// # Implementation details
+    /// The global index refers to the order of [`Frontier::iter`], and the
+    /// sub-vector is found with a binary search over the cumulative lengths
+    /// of the sub-vectors, the same mapping used by [`Frontier::get`].
+    /// Returns `None` when the index is out of range.
+    ///
+    /// # Arguments
+    /// * `global_index`: usize - Global index of the element.
+    pub fn shard_of(&self, global_index: usize) -> Option<(usize, usize)> {
+        self.locate(global_index)
+    }
+
+    #[inline]
+    /// Returns the global index of the first element of each sub-vector.
+    ///
+    /// # Implementation details
+    /// The table is cached and only recomputed after the sub-vectors may have
+    /// been modified, so iterating repeatedly over an unchanged frontier
+    /// does not walk the sub-vectors every time.
+    pub(crate) fn cumulative_lens(&self) -> Arc<Vec<usize>> {
+        self.data.cumulative_lens(Vec::len)
+    }
+
+    #[inline]
+    /// Executes the provided operation within the frontier [`ThreadPool`], if any.
+    pub(crate) fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        install_in(self.threads, op)
     }
 
     #[inline(always)]
+    /// Returns the index of the sub-vector of the calling thread.
+    ///
+    /// # Panics
+    /// If the index is not smaller than the number of sub-vectors, e.g. when
+    /// pushing from a pool with more threads than the frontier has sub-vectors.
     fn get_current_thread_index(&self) -> usize {
-        if let Some(thread_pool) = self.threads {
-            // We are using a custom ThreadPool so we want the call to come
-            // from the same ThreadPool or from the main thread.
-            if let Some(index) = thread_pool.current_thread_index() {
-                // The call is from the custom ThreadPool
-                index
-            } else {
-                // The call is from outside the custom ThreadPool so we want
-                // it to originate from no pool at all
-                if rayon::current_thread_index().is_some() {
-                    panic!("Parallel frontier called from external thread pool")
-                } else {
-                    0
-                }
-            }
+        let thread_id = if let Some(index_fn) = &self.index_fn {
+            // The user provided its own thread index resolution
+            index_fn()
         } else {
-            // We are not using a custom ThreadPool so the global one is used
-            rayon::current_thread_index().unwrap_or(0)
-        }
+            current_thread_index(self.threads)
+        };
+        assert!(
+            thread_id < self.number_of_threads(),
+            "the current thread index is {} but the frontier has {} sub-vectors",
+            thread_id,
+            self.number_of_threads()
+        );
+        thread_id
     }
 
     #[inline]
@@ -167,7 +590,128 @@ impl<'a, T> Frontier<'a, T> {
     /// * `value`: T - Object to be pushed onto of the frontier.
     pub fn push(&self, value: T) {
         let thread_id = self.get_current_thread_index();
-        unsafe { (*((&self.data[thread_id]) as *const Vec<T> as *mut Vec<T>)).push(value) };
+        unsafe { (*self.data.shard_ptr(thread_id)).push(value) };
+    }
+
+    #[inline]
+    /// Try to push value onto frontier, respecting the per sub-vector cap.
+    ///
+    /// # Implementation details
+    /// When the frontier was created with [`Frontier::with_bounded_capacity`]
+    /// and the sub-vector of the current thread already holds the maximum
+    /// number of elements, the value is returned back to the caller.
+    /// On unbounded frontiers this method behaves exactly like `push`.
+    ///
+    /// # Arguments
+    /// * `value`: T - Object to be pushed onto of the frontier.
+    pub fn try_push(&self, value: T) -> Result<(), T> {
+        let thread_id = self.get_current_thread_index();
+        let vector = self.data.shard_ptr(thread_id);
+        if let Some(shard_capacity) = self.shard_capacity {
+            if unsafe { (*vector).len() } >= shard_capacity {
+                return Err(value);
+            }
+        }
+        unsafe { (*vector).push(value) };
+        Ok(())
+    }
+
+    #[inline]
+    /// Push value onto frontier only if it was not already pushed through this method.
+    ///
+    /// Returns whether the value was pushed.
+    ///
+    /// # Implementation details
+    /// The elements pushed through this method are recorded in a concurrent
+    /// set, split in independently locked shards by element hash, which is
+    /// only allocated on the first call and is reset by `clear`, `par_clear`,
+    /// `drain` and `drain_into_vec`.
+    /// Elements pushed through `push` are not recorded and are not checked.
+    /// Do note that the set holds a clone of every unique element, so this
+    /// roughly doubles the memory required by the frontier: when duplicates
+    /// are rare or memory is tight, consider pushing everything and filtering
+    /// the duplicates afterward instead.
+    ///
+    /// # Arguments
+    /// * `value`: T - Object to be pushed onto of the frontier.
+    pub fn push_unique(&self, value: T) -> bool
+    where
+        T: Eq + Hash + Clone,
+    {
+        if self.seen.insert(&value) {
+            self.push(value);
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    /// Push value onto the sub-vectors in round-robin order.
+    ///
+    /// # Implementation details
+    /// Differently from `push`, which puts all the values pushed outside of
+    /// a thread pool onto the first sub-vector, the sub-vector is chosen by
+    /// an internal counter, wrapping around the number of sub-vectors, so
+    /// seeding the frontier from a single thread leaves the sub-vector
+    /// lengths differing by at most one.
+    /// This requires a mutable reference: since the sub-vector does not
+    /// depend on the calling thread, concurrent calls could push onto the same sub-vector.
+    ///
+    /// # Arguments
+    /// * `value`: T - Object to be pushed onto of the frontier.
+    pub fn push_balanced(&mut self, value: T) {
+        let thread_id = self.next_balanced_shard % self.number_of_threads();
+        self.next_balanced_shard = (thread_id + 1) % self.number_of_threads();
+        self.data[thread_id].push(value);
+    }
+
+    #[inline]
+    /// Replaces the content of each sub-vector with the one returned by `f` for its index.
+    ///
+    /// # Implementation details
+    /// The closure is called once for each sub-vector, in increasing index
+    /// order, and the previous elements of the sub-vector are dropped.
+    /// This is handy to seed the frontier deterministically, e.g. in tests,
+    /// without reaching into the sub-vectors through `as_mut`.
+    ///
+    /// # Arguments
+    /// * `f`: F - Closure returning the new content of the sub-vector with the given index.
+    pub fn fill_shards_from<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize) -> Vec<T>,
+    {
+        self.data
+            .iter_mut()
+            .enumerate()
+            .for_each(|(thread_id, vector)| *vector = f(thread_id));
+    }
+
+    #[inline]
+    /// Push all the values of the iterator onto the sub-vector with the given index.
+    ///
+    /// # Implementation details
+    /// The values are appended with `Vec::extend`, which reserves the capacity
+    /// for the lower bound of the size hint of the iterator at once, so
+    /// this is much faster than pushing the values one by one when seeding
+    /// the sub-vectors manually.
+    ///
+    /// # Arguments
+    /// * `iter`: I - Values to be pushed onto the sub-vector.
+    /// * `thread_id`: usize - Index of the sub-vector to push to.
+    ///
+    /// # Safety
+    /// No other thread may access the sub-vector `thread_id` while this
+    /// method runs, i.e. it must not be used concurrently with pushes or
+    /// pops of the thread the sub-vector is assigned to.
+    ///
+    /// # Panics
+    /// If `thread_id` is not smaller than the number of sub-vectors.
+    pub unsafe fn extend_on_thread<I>(&self, iter: I, thread_id: usize)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        (*self.data.shard_ptr(thread_id)).extend(iter);
     }
 
     #[inline]
@@ -179,9 +723,113 @@ impl<'a, T> Frontier<'a, T> {
     /// thread handle the pop from their subvector.
     /// When the `pop` method is called outside of a Rayon thread pool
     /// we simply pop objects from the first element in the pool.
+    /// The levels recorded by [`Frontier::mark_level`] are not updated.
     pub fn pop(&self) -> Option<T> {
         let thread_id = self.get_current_thread_index();
-        unsafe { (*((&self.data[thread_id]) as *const Vec<T> as *mut Vec<T>)).pop() }
+        unsafe { (*self.data.shard_ptr(thread_id)).pop() }
+    }
+
+    #[inline]
+    /// Pop element from the sub-vector with the given index.
+    ///
+    /// # Implementation details
+    /// Differently from `pop`, the sub-vector is chosen explicitly and not
+    /// by the calling thread, which is sound since the frontier is borrowed mutably.
+    /// Returns `None` when the index is out of range or the sub-vector is empty.
+    ///
+    /// # Arguments
+    /// * `thread_id`: usize - Index of the sub-vector to pop from.
+    pub fn pop_shard(&mut self, thread_id: usize) -> Option<T> {
+        self.data.get_mut(thread_id)?.pop()
+    }
+
+    #[inline]
+    /// Removes the element at the given position of the given sub-vector, replacing it with the last one.
+    ///
+    /// # Implementation details
+    /// As `Vec::swap_remove`, this is O(1) but does not preserve the order of the sub-vector.
+    /// Returns `None` when either index is out of range.
+    ///
+    /// # Arguments
+    /// * `thread_id`: usize - Index of the sub-vector to remove from.
+    /// * `idx`: usize - Position of the element within the sub-vector.
+    pub fn swap_remove(&mut self, thread_id: usize, idx: usize) -> Option<T> {
+        let vector = self.data.get_mut(thread_id)?;
+        if idx < vector.len() {
+            Some(vector.swap_remove(idx))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    /// Returns the last element of the current thread sub-vector, i.e. the one `pop` would remove.
+    ///
+    /// # Implementation details
+    /// Like `pop`, this method is thread-local: it only looks at the sub-vector
+    /// of the current thread, and returns `None` when that sub-vector is empty
+    /// even if other sub-vectors are not.
+    /// When the `peek_last` method is called outside of a Rayon thread pool
+    /// we simply look at the first element in the pool.
+    ///
+    /// # Safety
+    /// The sub-vector of the current thread must not be pushed to or popped
+    /// from while the returned reference is alive, not even by the current
+    /// thread, as that may move or drop the referenced element.
+    pub unsafe fn peek_last(&self) -> Option<&T> {
+        (*self.data.shard_ptr(self.get_current_thread_index())).last()
+    }
+
+    #[inline]
+    /// Returns the first element of the current thread sub-vector.
+    ///
+    /// # Implementation details
+    /// Like `pop`, this method is thread-local: it only looks at the sub-vector
+    /// of the current thread, and returns `None` when that sub-vector is empty
+    /// even if other sub-vectors are not.
+    /// When the `peek_first` method is called outside of a Rayon thread pool
+    /// we simply look at the first element in the pool.
+    ///
+    /// # Safety
+    /// The sub-vector of the current thread must not be pushed to or popped
+    /// from while the returned reference is alive, not even by the current
+    /// thread, as that may move or drop the referenced element.
+    pub unsafe fn peek_first(&self) -> Option<&T> {
+        (*self.data.shard_ptr(self.get_current_thread_index())).first()
+    }
+
+    #[inline]
+    /// Returns the element `pop` would remove, without removing it.
+    ///
+    /// # Implementation details
+    /// This mirrors `Vec::last` on the sub-vector of the current thread, and
+    /// is the same as [`Frontier::peek_last`].
+    ///
+    /// # Safety
+    /// As for [`Frontier::peek_last`], the sub-vector of the current thread
+    /// must not be pushed to or popped from while the returned reference is alive.
+    pub unsafe fn peek(&self) -> Option<&T> {
+        self.peek_last()
+    }
+
+    #[inline]
+    /// Returns the last element of the sub-vector with the given index, without removing it.
+    ///
+    /// # Implementation details
+    /// This mirrors `Vec::last` on the chosen sub-vector, i.e. it returns the
+    /// element `pop` would remove when called from the thread the sub-vector is assigned to.
+    ///
+    /// # Arguments
+    /// * `thread_id`: usize - Index of the sub-vector to look at.
+    ///
+    /// # Safety
+    /// No other thread may push to or pop from the sub-vector `thread_id`
+    /// while the returned reference is alive.
+    ///
+    /// # Panics
+    /// If `thread_id` is not smaller than the number of sub-vectors.
+    pub unsafe fn peek_on_thread(&self, thread_id: usize) -> Option<&T> {
+        (*self.data.shard_ptr(thread_id)).last()
     }
 
     #[inline]
@@ -190,6 +838,12 @@ impl<'a, T> Frontier<'a, T> {
         self.data.len()
     }
 
+    #[inline]
+    /// Returns the [`ThreadPool`] the frontier was created for, if any.
+    pub fn thread_pool(&self) -> Option<&'a ThreadPool> {
+        self.threads
+    }
+
     #[inline]
     /// Returns system number of the threads, i.e. subvectors, in frontier objects without a user [`ThreadPool`].
     pub fn system_number_of_threads() -> usize {
@@ -210,14 +864,543 @@ impl<'a, T> Frontier<'a, T> {
 
     #[inline]
     /// Clears all sub-vectors, maintaining the reached vector capacity.
+    ///
+    /// # Implementation details
+    /// This also resets the set of the elements seen by [`Frontier::push_unique`]
+    /// and the levels recorded by [`Frontier::mark_level`].
     pub fn clear(&mut self) {
         self.data.iter_mut().for_each(|v| v.clear());
+        self.seen.clear();
+        self.level_boundaries.clear();
     }
 
     #[inline]
-    /// Shrinks to fit all sub-vectors.
-    pub fn shrink_to_fit(&mut self) {
-        self.data.iter_mut().for_each(|v| v.shrink_to_fit());
+    /// Closes the current level, recording the current length of the frontier as its end.
+    ///
+    /// # Implementation details
+    /// This is meant for traversals pushing several levels onto the same
+    /// frontier: only the number of elements of each level is recorded, as
+    /// the elements of a level are spread across the sub-vectors and do not
+    /// form a contiguous range in the [`Frontier::iter`] order.
+    /// Removing elements invalidates the recorded levels: `clear`, `par_clear`,
+    /// `drain` and `drain_into_vec` reset them, `truncate` drops the levels
+    /// ending past the new length, while the methods removing arbitrary
+    /// elements, such as `pop`, `retain_mut` or `extract_if`, leave them untouched.
+    pub fn mark_level(&mut self) {
+        let len = self.len();
+        self.level_boundaries.push(len);
+    }
+
+    #[inline]
+    /// Returns the number of elements of each level closed by [`Frontier::mark_level`].
+    ///
+    /// # Implementation details
+    /// The elements pushed after the last call to `mark_level` belong to a
+    /// level that is still open, and are not counted.
+    pub fn level_lengths(&self) -> Vec<usize> {
+        self.level_boundaries
+            .iter()
+            .scan(0, |start, &end| {
+                let len = end.saturating_sub(*start);
+                *start = end;
+                Some(len)
+            })
+            .collect()
+    }
+
+    #[inline]
+    /// Removes the elements matching the predicate, returning an iterator over them.
+    ///
+    /// # Implementation details
+    /// The sub-vectors are visited in order, so the elements are yielded in
+    /// the [`Frontier::iter`] order and the retained ones keep their relative order.
+    /// The elements are removed lazily: if the iterator is dropped before
+    /// being exhausted, the elements not yet visited stay in the frontier.
+    ///
+    /// # Arguments
+    /// * `pred`: F - Closure returning whether an element has to be extracted.
+    pub fn extract_if<'b, F>(&'b mut self, pred: F) -> impl Iterator<Item = T> + 'b
+    where
+        F: FnMut(&T) -> bool + 'b,
+    {
+        // only one sub-vector is drained at a time, so the borrow is never shared
+        let pred = Rc::new(RefCell::new(pred));
+        self.data.iter_mut().flat_map(move |vector| {
+            let pred = pred.clone();
+            vector.extract_if(.., move |value| (pred.borrow_mut())(value))
+        })
+    }
+
+    #[inline]
+    /// Removes all the elements, returning an iterator over them.
+    ///
+    /// # Implementation details
+    /// The elements are yielded in the [`Frontier::iter`] order, and the
+    /// iterator is double-ended, so the most recently pushed elements of the
+    /// last sub-vector can be processed first.
+    /// The sub-vectors keep their capacity, and are left empty when the
+    /// iterator is dropped, even if it was not exhausted.
+    /// This also resets the set of the elements seen by [`Frontier::push_unique`]
+    /// and the levels recorded by [`Frontier::mark_level`], as `clear` does.
+    pub fn drain(&mut self) -> FrontierDrain<'_, T> {
+        self.seen.clear();
+        self.level_boundaries.clear();
+        FrontierDrain::new(&mut self.data)
+    }
+
+    #[inline]
+    /// Moves all the elements into a new vector, in the [`Frontier::iter`] order.
+    ///
+    /// # Implementation details
+    /// The vector is allocated once with exactly `len` capacity, and the
+    /// elements are moved out of each sub-vector with `Vec::append`, so
+    /// nothing is cloned and the sub-vectors are left empty but keep their capacity.
+    /// This also resets the set of the elements seen by [`Frontier::push_unique`]
+    /// and the levels recorded by [`Frontier::mark_level`], as `clear` does.
+    pub fn drain_into_vec(&mut self) -> Vec<T> {
+        self.seen.clear();
+        self.level_boundaries.clear();
+        let mut result = Vec::with_capacity(self.len());
+        self.data
+            .iter_mut()
+            .for_each(|vector| result.append(vector));
+        result
+    }
+
+    #[inline]
+    /// Retains only the elements for which `f` returns `true`, passing a mutable reference to them.
+    ///
+    /// # Implementation details
+    /// Each sub-vector is filtered in place with `Vec::retain_mut`, so the
+    /// order of the retained elements and the capacities are preserved.
+    /// The levels recorded by [`Frontier::mark_level`] are not updated.
+    ///
+    /// # Arguments
+    /// * `f`: F - Closure possibly modifying an element and returning whether to keep it.
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.data
+            .iter_mut()
+            .for_each(|vector| vector.retain_mut(&mut f));
+    }
+
+    #[inline]
+    /// Removes the consecutive elements of each sub-vector that resolve to the same key.
+    ///
+    /// # Implementation details
+    /// This applies `Vec::dedup_by_key` to each sub-vector, so only the
+    /// duplicates that are adjacent within the same sub-vector are removed,
+    /// keeping the first of them, while equal keys in different sub-vectors are all kept.
+    /// To remove the duplicates across the whole frontier, collect it with
+    /// [`Frontier::to_sorted_vec`] and call `Vec::dedup_by_key` on the result;
+    /// to avoid pushing duplicates in the first place see [`Frontier::push_unique`].
+    ///
+    /// # Arguments
+    /// * `key`: F - Closure returning the key of an element.
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&mut T) -> K,
+    {
+        self.data
+            .iter_mut()
+            .for_each(|vector| vector.dedup_by_key(&mut key));
+    }
+
+    #[inline]
+    /// Retains only the elements for which `f` returns `true`, filtering the sub-vectors in parallel.
+    ///
+    /// # Implementation details
+    /// As [`Frontier::retain_mut`], but each sub-vector is filtered by a
+    /// different task within the frontier [`ThreadPool`], if any.
+    ///
+    /// # Arguments
+    /// * `f`: F - Closure possibly modifying an element and returning whether to keep it.
+    pub fn par_retain_mut<F>(&mut self, f: F)
+    where
+        T: Send,
+        F: Fn(&mut T) -> bool + Sync + Send,
+    {
+        let data = &mut self.data;
+        install_in(self.threads, || {
+            data.par_iter_mut()
+                .for_each(|vector| vector.retain_mut(|value| f(value)))
+        });
+    }
+
+    #[inline]
+    /// Keeps the first `len` elements in the [`Frontier::iter`] order, dropping the rest.
+    ///
+    /// # Implementation details
+    /// The sub-vector containing the element at index `len` is truncated and
+    /// all the following sub-vectors are cleared, maintaining their capacity.
+    /// When `len` is greater than the length of the frontier, this has no effect.
+    /// The levels recorded by [`Frontier::mark_level`] ending past `len` are
+    /// dropped, so the level `len` falls into is open again.
+    ///
+    /// # Arguments
+    /// * `len`: usize - Number of elements to keep.
+    pub fn truncate(&mut self, len: usize) {
+        self.level_boundaries.retain(|&end| end <= len);
+        let mut remaining = len;
+        for vector in self.data.iter_mut() {
+            if remaining < vector.len() {
+                vector.truncate(remaining);
+            }
+            remaining -= vector.len();
+        }
+    }
+
+    #[inline]
+    /// Keeps the first `len` elements of the sub-vector with the given index, dropping the rest.
+    ///
+    /// # Arguments
+    /// * `thread_id`: usize - Index of the sub-vector to truncate.
+    /// * `len`: usize - Number of elements to keep.
+    ///
+    /// # Panics
+    /// If `thread_id` is not smaller than the number of sub-vectors.
+    pub fn truncate_shard(&mut self, thread_id: usize, len: usize) {
+        self.data[thread_id].truncate(len);
+    }
+
+    #[inline]
+    /// Splits the frontier into two at the given global index.
+    ///
+    /// Returns a new frontier containing the elements in the range `[at, len)`
+    /// of the [`Frontier::iter`] order, while this frontier keeps the
+    /// elements in the range `[0, at)`.
+    ///
+    /// # Implementation details
+    /// The returned frontier has the same number of sub-vectors of this one:
+    /// the tail of the sub-vector containing the element at `at` and all the
+    /// following sub-vectors are moved, in place, into the new frontier.
+    ///
+    /// # Arguments
+    /// * `at`: usize - Global index where to split the frontier.
+    ///
+    /// # Panics
+    /// If `at` is greater than the length of the frontier.
+    pub fn split_off(&mut self, at: usize) -> Frontier<'static, T> {
+        let len = self.len();
+        assert!(
+            at <= len,
+            "`at` split index (is {}) should be <= len (is {})",
+            at,
+            len
+        );
+        let mut tail = (0..self.number_of_threads())
+            .map(|_| Vec::new())
+            .collect::<Vec<_>>();
+        if let Some((thread_id, offset)) = self.locate(at) {
+            tail[thread_id] = self.data[thread_id].split_off(offset);
+            for (vector, tail_vector) in self.data[thread_id + 1..]
+                .iter_mut()
+                .zip(tail[thread_id + 1..].iter_mut())
+            {
+                *tail_vector = core::mem::take(vector);
+            }
+        }
+        Frontier::from_shards(tail)
+    }
+
+    #[inline]
+    /// Merges two frontiers whose elements are sorted in the [`Frontier::iter`] order into a sorted frontier.
+    ///
+    /// # Implementation details
+    /// The two sequences are merged sequentially in linear time, taking the
+    /// element of this frontier first on ties, so the merge is stable.
+    /// The result is rebalanced over as many sub-vectors as this frontier,
+    /// with the same configuration: the sorted sequence is cut in contiguous
+    /// runs whose lengths differ by at most one, the same way
+    /// [`Frontier::with_capacity`] distributes the capacity, so the result is still
+    /// sorted in the [`Frontier::iter`] order and can be processed in parallel.
+    /// Do note that the order of the inputs is not checked: when they are
+    /// not sorted, the result is not sorted either.
+    ///
+    /// # Arguments
+    /// * `other`: `Frontier<T>` - Sorted frontier to merge with this one.
+    pub fn merge_sorted(mut self, other: Frontier<'_, T>) -> Self
+    where
+        T: Ord,
+    {
+        let n_threads = self.number_of_threads();
+        let len = self.len() + other.len();
+        let mut left = core::mem::take(&mut self.data)
+            .into_iter()
+            .flatten()
+            .peekable();
+        let mut right = other.data.into_iter().flatten().peekable();
+        let mut data = Frontier::<T>::shards_with_capacity(n_threads, len);
+        for (thread_id, vector) in data.iter_mut().enumerate() {
+            for _ in 0..capacity_share(len, n_threads, thread_id) {
+                let from_right = match (left.peek(), right.peek()) {
+                    (Some(l), Some(r)) => r < l,
+                    (None, _) => true,
+                    (_, None) => false,
+                };
+                let value = if from_right {
+                    right.next()
+                } else {
+                    left.next()
+                };
+                vector.extend(value);
+            }
+        }
+        Frontier {
+            threads: self.threads,
+            index_fn: self.index_fn,
+            shard_capacity: self.shard_capacity,
+            ..Frontier::from_shards(data)
+        }
+    }
+
+    #[inline]
+    /// Moves all the elements of the other frontier into this one, merging the sub-vectors in parallel.
+    ///
+    /// # Implementation details
+    /// Each sub-vector of the other frontier is appended to the sub-vector
+    /// with the same index of this frontier, in parallel within the frontier
+    /// [`ThreadPool`] if any, so the two frontiers must have the same number of sub-vectors.
+    ///
+    /// # Arguments
+    /// * `other`: `Frontier<T>` - Frontier whose elements are moved into this one.
+    pub fn par_extend_from(&mut self, other: Frontier<'_, T>) -> Result<(), String>
+    where
+        T: Send,
+    {
+        if self.number_of_threads() != other.number_of_threads() {
+            return Err(format!(
+                concat!(
+                    "You have provided a frontier with {} sub-vectors ",
+                    "to be merged into a Frontier object, but to do ",
+                    "so we expected exactly {} sub-vectors."
+                ),
+                other.number_of_threads(),
+                self.number_of_threads()
+            ));
+        }
+        let data = &mut self.data;
+        install_in(self.threads, || {
+            data.par_iter_mut()
+                .zip(other.data.into_vec())
+                .for_each(|(vector, mut other_vector)| vector.append(&mut other_vector))
+        });
+        Ok(())
+    }
+
+    #[inline]
+    /// Redistributes the elements so that the sub-vector lengths differ by at most one.
+    ///
+    /// # Implementation details
+    /// The [`Frontier::iter`] order of the elements is preserved, and the
+    /// first sub-vectors receive one more element when the length of the frontier
+    /// is not a multiple of the number of sub-vectors.
+    /// Each element is moved exactly once into a new sub-vector allocated
+    /// with the exact required capacity, so the previously reached capacities are released.
+    /// This is useful after a skewed push phase, as it allows the parallel
+    /// iterators to split the work evenly.
+    pub fn rebalance(&mut self) {
+        let n_threads = self.number_of_threads();
+        let len = self.len();
+        let mut values = core::mem::take(&mut self.data).into_iter().flatten();
+        self.data = (0..n_threads)
+            .map(|thread_id| {
+                let size = capacity_share(len, n_threads, thread_id);
+                let mut vector = Vec::with_capacity(size);
+                vector.extend(values.by_ref().take(size));
+                vector
+            })
+            .collect::<Vec<_>>()
+            .into();
+    }
+
+    #[inline]
+    /// Reverses the order of the elements of the frontier in place.
+    ///
+    /// # Implementation details
+    /// Both the order of the sub-vectors and the content of each sub-vector
+    /// are reversed, so that [`Frontier::iter`] yields the elements in
+    /// exactly the opposite order, without moving them across sub-vectors.
+    /// Do note that after this the sub-vector of the thread `i` holds what
+    /// was pushed by the thread `n - 1 - i`.
+    pub fn reverse(&mut self) {
+        self.data.reverse();
+        self.data.iter_mut().for_each(|vector| vector.reverse());
+    }
+
+    #[inline]
+    /// Reserves capacity for at least `additional` more elements in the sub-vector with the given index.
+    ///
+    /// # Implementation details
+    /// Differently from the uniform reservation of [`Frontier::with_capacity`],
+    /// only the given sub-vector grows, which suits skewed workloads where a
+    /// single worker is known to receive most of the elements.
+    ///
+    /// # Arguments
+    /// * `thread_id`: usize - Index of the sub-vector to grow.
+    /// * `additional`: usize - Number of additional elements to reserve space for.
+    ///
+    /// # Panics
+    /// If `thread_id` is not smaller than the number of sub-vectors, as a
+    /// wrong index is a logic error rather than an expected condition.
+    pub fn reserve_shard(&mut self, thread_id: usize, additional: usize) {
+        let n_threads = self.number_of_threads();
+        assert!(
+            thread_id < n_threads,
+            "the sub-vector index is {} but the frontier has {} sub-vectors",
+            thread_id,
+            n_threads
+        );
+        self.data[thread_id].reserve(additional);
+    }
+
+    #[inline]
+    /// Replaces the given range of the sub-vector with the given index, returning the removed elements.
+    ///
+    /// # Implementation details
+    /// This delegates to `Vec::splice`, so the replacement happens in place
+    /// and the removed elements are yielded lazily; as with `Vec::splice`,
+    /// the range is removed even if the returned iterator is not consumed.
+    ///
+    /// # Arguments
+    /// * `thread_id`: usize - Index of the sub-vector to edit.
+    /// * `range`: `Range<usize>` - Range of the elements to remove from the sub-vector.
+    /// * `replace_with`: I - Elements to insert in place of the removed ones.
+    ///
+    /// # Panics
+    /// If `thread_id` is not smaller than the number of sub-vectors, or if
+    /// the range is decreasing or ends after the end of the sub-vector.
+    pub fn splice_shard<I>(
+        &mut self,
+        thread_id: usize,
+        range: core::ops::Range<usize>,
+        replace_with: I,
+    ) -> std::vec::Splice<'_, I::IntoIter>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let len = self.shard_len(thread_id);
+        assert!(
+            range.start <= range.end && range.end <= len,
+            "the range is {:?} but the sub-vector {} has {} elements",
+            range,
+            thread_id,
+            len
+        );
+        self.data[thread_id].splice(range, replace_with)
+    }
+
+    #[inline]
+    /// Shrinks all sub-vectors, retaining a total capacity of at least `min_capacity`.
+    ///
+    /// # Implementation details
+    /// The capacity is distributed across the sub-vectors as in [`Frontier::with_capacity`],
+    /// and each sub-vector is shrunk with `Vec::shrink_to`: sub-vectors whose
+    /// length exceeds their share keep a capacity of at least their length,
+    /// while sub-vectors whose capacity is already below their share are left untouched.
+    ///
+    /// # Arguments
+    /// * `min_capacity`: usize - Total capacity to retain across the sub-vectors.
+    pub fn shrink_shards_to(&mut self, min_capacity: usize) {
+        let n_threads = self.number_of_threads();
+        self.data.iter_mut().enumerate().for_each(|(thread_id, v)| {
+            v.shrink_to(capacity_share(min_capacity, n_threads, thread_id))
+        });
+    }
+
+    #[inline]
+    /// Converts the frontier into a new frontier with the same layout, applying `f` to every element.
+    ///
+    /// # Implementation details
+    /// The returned frontier has the same number of sub-vectors and the same
+    /// number of elements in each of them, so the [`Frontier::iter`] order is preserved.
+    /// The sub-vectors are converted in parallel.
+    ///
+    /// # Arguments
+    /// * `f`: F - Closure converting each element.
+    pub fn map_into<U, F>(mut self, f: F) -> Frontier<'static, U>
+    where
+        T: Send,
+        U: Send,
+        F: Fn(T) -> U + Sync + Send,
+    {
+        let data = core::mem::take(&mut self.data).into_vec();
+        Frontier::from_shards(self.install(|| {
+            data.into_par_iter()
+                .map(|vector| vector.into_iter().map(&f).collect::<Vec<U>>())
+                .collect::<Vec<Vec<U>>>()
+        }))
+    }
+
+    #[inline]
+    /// Converts the frontier into a sorted vector of its elements.
+    ///
+    /// # Implementation details
+    /// Differently from the `From` conversion, the order of the result does
+    /// not depend on how the elements are distributed across the sub-vectors.
+    pub fn into_sorted_vec(self) -> Vec<T>
+    where
+        T: Ord,
+    {
+        let mut values = self.data.into_iter().flatten().collect::<Vec<_>>();
+        values.sort();
+        values
+    }
+
+    #[inline]
+    /// Clears all sub-vectors in parallel, maintaining the reached vector capacity.
+    ///
+    /// # Implementation details
+    /// The elements of each sub-vector are dropped in parallel, within the
+    /// frontier [`ThreadPool`] if any. This is faster than `clear` when there
+    /// are many elements and `T` has a non-trivial `Drop`, as for `String`s
+    /// or boxed payloads, while for trivially droppable elements `clear`
+    /// avoids the parallel dispatch and should be preferred.
+    /// This also resets the set of the elements seen by [`Frontier::push_unique`]
+    /// and the levels recorded by [`Frontier::mark_level`], as `clear` does.
+    pub fn par_clear(&mut self)
+    where
+        T: Send,
+    {
+        let data = &mut self.data;
+        install_in(self.threads, || data.par_iter_mut().for_each(|v| v.clear()));
+        self.seen.clear();
+        self.level_boundaries.clear();
+    }
+
+    #[inline]
+    /// Shrinks to fit all sub-vectors.
+    pub fn shrink_to_fit(&mut self) {
+        self.data.iter_mut().for_each(|v| v.shrink_to_fit());
+    }
+
+    #[inline]
+    /// Removes the empty sub-vectors, keeping at least one.
+    ///
+    /// # Implementation details
+    /// The remaining sub-vectors keep their relative order, so the order of
+    /// [`Frontier::iter`] is preserved, while iterating and splitting the
+    /// parallel iterators have fewer sub-vector boundaries to cross.
+    /// Do note that this changes `number_of_threads`, and so the sub-vector
+    /// each thread pushes onto: it should only be used once the frontier is
+    /// no longer being filled, as pushing from a thread whose index is not
+    /// smaller than the new number of sub-vectors panics.
+    pub fn retain_shards(&mut self) {
+        let mut data = core::mem::take(&mut self.data).into_vec();
+        let first = data
+            .iter()
+            .position(|vector| !vector.is_empty())
+            .unwrap_or(0);
+        let mut i = 0;
+        data.retain(|vector| {
+            let keep = i == first || !vector.is_empty();
+            i += 1;
+            keep
+        });
+        self.data = Shards::from(data);
     }
 
     #[inline]
@@ -226,35 +1409,608 @@ impl<'a, T> Frontier<'a, T> {
         FrontierIter::new(self)
     }
 
+    #[inline]
+    /// Converts the frontier into a sequential iterator of the elements, in reverse order.
+    ///
+    /// # Implementation details
+    /// This is the same as `self.iter().rev()`, so the elements are yielded
+    /// from the last element of the last sub-vector to the first element of the first one.
+    pub fn rev_iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.iter().rev()
+    }
+
+    #[inline]
+    /// Iter the elements sequentially in groups of `size` elements.
+    ///
+    /// # Implementation details
+    /// The chunks follow the same order of [`Frontier::iter`], and since
+    /// the sub-vectors are not contiguous in memory a chunk may straddle
+    /// two or more sub-vectors, so each chunk is returned as a vector of references.
+    /// The last chunk is shorter when `size` does not divide the frontier length.
+    ///
+    /// # Arguments
+    /// * `size`: usize - Number of elements in each chunk.
+    ///
+    /// # Panics
+    /// If `size` is zero.
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = Vec<&T>> + '_ {
+        assert!(size != 0, "chunk size must be non-zero");
+        let mut iter = self.iter();
+        core::iter::from_fn(move || {
+            let chunk = iter.by_ref().take(size).collect::<Vec<_>>();
+            if chunk.is_empty() {
+                None
+            } else {
+                Some(chunk)
+            }
+        })
+    }
+
+    #[inline]
+    /// Iter sequentially all the overlapping windows of `size` consecutive elements.
+    ///
+    /// # Implementation details
+    /// The windows follow the same order of [`Frontier::iter`] and, as with
+    /// `slice::windows`, consecutive windows are shifted by one element.
+    /// Since the sub-vectors are not contiguous in memory, a window may span
+    /// two or more sub-vectors and cannot be a single slice, so each window
+    /// is returned as a vector of references.
+    /// When the frontier holds fewer than `size` elements no window is returned.
+    ///
+    /// # Arguments
+    /// * `size`: usize - Number of elements in each window.
+    ///
+    /// # Panics
+    /// If `size` is zero.
+    pub fn windows(&self, size: usize) -> impl Iterator<Item = Vec<&T>> + '_ {
+        assert!(size != 0, "window size must be non-zero");
+        let mut iter = self.iter();
+        let mut window = iter
+            .by_ref()
+            .take(size - 1)
+            .collect::<std::collections::VecDeque<_>>();
+        iter.map(move |value| {
+            window.push_back(value);
+            let result = window.iter().copied().collect::<Vec<_>>();
+            window.pop_front();
+            result
+        })
+    }
+
+    #[inline]
+    /// Returns the element at the provided global index, if any.
+    ///
+    /// # Implementation details
+    /// The index refers to the global order of [`Frontier::iter`], and the
+    /// lookup is a binary search over the cumulative lengths of the
+    /// sub-vectors, see [`Frontier::shard_of`], so it does not depend on the number of elements.
+    /// Do note that random access is only meaningful once pushing has
+    /// finished, as any push shifts the index of the following elements.
+    /// Moreover, a push may reallocate the sub-vector holding the returned
+    /// element, so, as for [`Frontier::peek_last`], no thread may push while
+    /// the returned reference is alive.
+    ///
+    /// # Arguments
+    /// * `index`: usize - Global index of the element.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.locate(index)
+            .map(|(thread_id, offset)| &self.data[thread_id][offset])
+    }
+
+    #[inline]
+    /// Returns the first element of the frontier in the order of [`Frontier::iter`].
+    ///
+    /// # Implementation details
+    /// Unlike [`Frontier::peek_first`], this looks at all the sub-vectors,
+    /// skipping the empty leading ones.
+    /// As with `peek_first`, a push may reallocate the sub-vector holding the
+    /// returned element, so no thread may push while the reference is alive.
+    pub fn first(&self) -> Option<&T> {
+        self.data.iter().find_map(|vector| vector.first())
+    }
+
+    #[inline]
+    /// Returns the last element of the frontier in the order of [`Frontier::iter`].
+    ///
+    /// # Implementation details
+    /// Unlike [`Frontier::peek_last`], this looks at all the sub-vectors,
+    /// skipping the empty trailing ones.
+    /// As with `peek_last`, a push may reallocate the sub-vector holding the
+    /// returned element, so no thread may push while the reference is alive.
+    pub fn last(&self) -> Option<&T> {
+        self.data.iter().rev().find_map(|vector| vector.last())
+    }
+
+    #[inline]
+    /// Returns the number of elements matching the predicate.
+    ///
+    /// # Arguments
+    /// * `predicate`: P - Closure returning whether an element matches.
+    pub fn count_if<P>(&self, predicate: P) -> usize
+    where
+        P: Fn(&T) -> bool,
+    {
+        self.data
+            .iter()
+            .map(|vector| vector.iter().filter(|value| predicate(value)).count())
+            .sum()
+    }
+
+    #[inline]
+    /// Returns the index of the first element matching the predicate, if any.
+    ///
+    /// # Implementation details
+    /// The index refers to the global order of [`Frontier::iter`], i.e. it
+    /// accounts for the elements of all the preceding sub-vectors.
+    ///
+    /// # Arguments
+    /// * `predicate`: P - Closure returning whether an element matches.
+    pub fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().position(predicate)
+    }
+
+    #[inline]
+    /// Iter the elements sequentially in slices of at most `size` contiguous elements.
+    ///
+    /// # Implementation details
+    /// Differently from [`Frontier::chunks`], the chunks never cross the
+    /// sub-vector boundaries, so they can be returned as slices without copying:
+    /// each sub-vector is split into chunks of exactly `size` elements
+    /// except for its last chunk, which is shorter when `size` does not divide
+    /// the sub-vector length. Empty sub-vectors yield no chunks.
+    ///
+    /// # Arguments
+    /// * `size`: usize - Maximum number of elements in each chunk.
+    ///
+    /// # Panics
+    /// If `size` is zero.
+    pub fn shard_chunks(&self, size: usize) -> impl Iterator<Item = &[T]> + '_ {
+        assert!(size != 0, "chunk size must be non-zero");
+        self.data.iter().flat_map(move |vector| vector.chunks(size))
+    }
+
+    #[inline]
+    /// Returns the slices of all the non-empty sub-vectors, in the [`Frontier::iter`] order.
+    ///
+    /// # Implementation details
+    /// Each slice is a contiguous run of elements, so it can be handed to
+    /// vectorized loops or foreign functions; the total number of elements is [`Frontier::len`].
+    /// The slices borrow the frontier, so they are only valid while no
+    /// pushes occur.
+    pub fn shard_slices(&self) -> Vec<&[T]> {
+        self.data
+            .iter()
+            .filter(|vector| !vector.is_empty())
+            .map(|vector| vector.as_slice())
+            .collect()
+    }
+
+    #[inline]
+    /// Iter the elements sequentially, together with the index of their sub-vector.
+    ///
+    /// # Implementation details
+    /// The elements are yielded in the [`Frontier::iter`] order, and the
+    /// index is the one of the thread the sub-vector is assigned to, which
+    /// is handy to spot the load imbalance between the workers.
+    pub fn iter_enumerate_shard(&self) -> impl Iterator<Item = (usize, &T)> + '_ {
+        self.data
+            .iter()
+            .enumerate()
+            .flat_map(|(thread_id, vector)| vector.iter().map(move |value| (thread_id, value)))
+    }
+
+    #[inline]
+    /// Returns all the elements as a single slice, if they are all in the same sub-vector.
+    ///
+    /// # Implementation details
+    /// This is a zero-copy fast path for the frontiers filled outside of any
+    /// thread pool, whose elements all end up in the first sub-vector.
+    /// After parallel pushes the elements are typically spread across more
+    /// sub-vectors, and `None` is returned: use [`Frontier::shard_slices`] instead.
+    /// An empty frontier, having no non-empty sub-vector, also returns `None`.
+    pub fn as_slice(&self) -> Option<&[T]> {
+        let mut non_empty = self.data.iter().filter(|vector| !vector.is_empty());
+        match (non_empty.next(), non_empty.next()) {
+            (Some(vector), None) => Some(vector.as_slice()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    /// Returns the number of elements of the sub-vector with the given index.
+    ///
+    /// # Arguments
+    /// * `thread_id`: usize - Index of the sub-vector.
+    ///
+    /// # Panics
+    /// If `thread_id` is not smaller than the number of sub-vectors.
+    pub fn shard_len(&self, thread_id: usize) -> usize {
+        let n_threads = self.number_of_threads();
+        assert!(
+            thread_id < n_threads,
+            "the sub-vector index is {} but the frontier has {} sub-vectors",
+            thread_id,
+            n_threads
+        );
+        self.data[thread_id].len()
+    }
+
     #[inline]
     /// Iter the sub-vectors sequentially.
     pub fn iter_vectors(&self) -> impl Iterator<Item = &Vec<T>> + '_ {
         self.data.iter()
     }
 
+    #[inline]
+    /// Iter the sub-vectors sequentially and mutably, together with their index.
+    pub fn shards_mut(&mut self) -> impl Iterator<Item = (usize, &mut Vec<T>)> + '_ {
+        self.data.iter_mut().enumerate()
+    }
+
     #[inline]
     /// Returns vector with the sizes of each subvector.
     pub fn vector_sizes(&self) -> Vec<usize> {
         self.data.iter().map(|v| v.len()).collect::<Vec<_>>()
     }
 
+    #[inline]
+    /// Returns vector with the capacities of each subvector.
+    pub fn shard_capacities(&self) -> Vec<usize> {
+        self.data.iter().map(|v| v.capacity()).collect::<Vec<_>>()
+    }
+
+    #[inline]
+    /// Returns an estimate of the heap memory allocated by the frontier, in bytes.
+    ///
+    /// # Implementation details
+    /// This is the capacity of every sub-vector times the size of `T`, plus
+    /// the table holding the sub-vectors themselves.
+    /// It is an estimate: the heap memory owned by the elements, e.g. the
+    /// buffers of a `Vec<T>` element, the set used by [`Frontier::push_unique`]
+    /// and the allocator bookkeeping are not included.
+    pub fn heap_bytes(&self) -> usize {
+        self.data
+            .iter()
+            .map(|vector| vector.capacity() * core::mem::size_of::<T>())
+            .sum::<usize>()
+            + self.number_of_threads() * core::mem::size_of::<Vec<T>>()
+    }
+
+    #[inline]
+    /// Returns an estimate of the heap memory allocated by the frontier, in bytes.
+    ///
+    /// # Implementation details
+    /// This is an alias of [`Frontier::heap_bytes`].
+    pub fn memory_footprint(&self) -> usize {
+        self.heap_bytes()
+    }
+
     #[inline]
     /// Converts the frontier into a parallel iterator of the elements.
+    ///
+    /// # Implementation details
+    /// When the frontier was created for a custom [`ThreadPool`], the
+    /// iterator is driven within that pool, independently of the pool
+    /// the consuming method is called from.
+    /// The indexed adaptors, e.g. `enumerate`, `zip`, `rev` and `with_max_len`,
+    /// and so also [`Frontier::par_chunks`] and [`Frontier::par_step_by`],
+    /// instead run in the pool of the caller, so to run them within the
+    /// frontier pool they have to be consumed inside `ThreadPool::install`.
     pub fn par_iter(&self) -> FrontierParIter<'_, T> {
         FrontierParIter::new(self)
     }
+
+    #[inline]
+    /// Converts the frontier into a parallel iterator of the elements, the efficient alternative to `iter().par_bridge()`.
+    ///
+    /// # Implementation details
+    /// This is the same as [`Frontier::par_iter`]: the iterator knows its
+    /// length and splits the elements by global index, through the
+    /// cumulative lengths of the sub-vectors, so it is indexed and each task
+    /// gets a contiguous run of elements without any synchronization.
+    /// On the other hand `par_bridge` cannot know that [`FrontierIter`] is
+    /// splittable, so its workers pull the elements one at a time from the
+    /// sequential iterator behind a lock, which serializes the iteration,
+    /// loses the order of the elements and does not support the indexed adaptors.
+    pub fn as_parallel(&self) -> FrontierParIter<'_, T> {
+        self.par_iter()
+    }
+
+    #[inline]
+    /// Iter the elements in parallel, never creating tasks smaller than `min_len` elements.
+    ///
+    /// # Implementation details
+    /// This is analogous to rayon's `with_min_len`: the iterator stops being
+    /// split once a part would hold less than `min_len` elements, which
+    /// avoids the scheduling overhead dominating when the per-element work is light.
+    /// A `min_len` of zero is treated as one.
+    ///
+    /// # Arguments
+    /// * `min_len`: usize - Minimum number of elements of each task.
+    pub fn par_iter_with_min_len(&self, min_len: usize) -> FrontierParIter<'_, T> {
+        FrontierParIter {
+            min_len: min_len.max(1),
+            ..FrontierParIter::new(self)
+        }
+    }
 }
 
 impl<'a, T> Frontier<'a, T>
 where
     T: Send + Sync,
 {
+    #[inline]
+    /// Iter the elements in parallel in groups of `size` elements.
+    ///
+    /// # Implementation details
+    /// The chunks are the same returned by [`Frontier::chunks`], as the
+    /// parallel iterator is only split on chunk boundaries.
+    /// Like the other indexed adaptors, this runs in the pool of the caller.
+    ///
+    /// # Arguments
+    /// * `size`: usize - Number of elements in each chunk.
+    ///
+    /// # Panics
+    /// If `size` is zero.
+    pub fn par_chunks(&self, size: usize) -> impl IndexedParallelIterator<Item = Vec<&T>> + '_ {
+        self.par_iter().chunks(size)
+    }
+
+    #[inline]
+    /// Calls `f` on every element in parallel, passing it a state created by `init`.
+    ///
+    /// # Implementation details
+    /// The work is split on the sub-vectors, so `init` is called at most once
+    /// per sub-vector and not once per element, which makes it suitable to
+    /// create scratch buffers or other expensive per-thread state.
+    /// This runs within the frontier [`ThreadPool`], if any.
+    ///
+    /// # Arguments
+    /// * `init`: I - Closure creating the state of each job.
+    /// * `f`: F - Closure called on every element with the state of its job.
+    pub fn par_for_each_init<S, I, F>(&self, init: I, f: F)
+    where
+        I: Fn() -> S + Sync + Send,
+        F: Fn(&mut S, &T) + Sync + Send,
+    {
+        self.install(|| {
+            self.data.par_iter().for_each_init(&init, |state, vector| {
+                vector.iter().for_each(|value| f(state, value))
+            })
+        })
+    }
+
+    #[inline]
+    /// Returns the number of elements matching the predicate, counting in parallel.
+    ///
+    /// # Implementation details
+    /// Each sub-vector is counted by a different task within the frontier
+    /// [`ThreadPool`], if any, and the per sub-vector counts are summed.
+    ///
+    /// # Arguments
+    /// * `predicate`: P - Closure returning whether an element matches.
+    pub fn par_count_if<P>(&self, predicate: P) -> usize
+    where
+        P: Fn(&T) -> bool + Sync + Send,
+    {
+        self.install(|| {
+            self.data
+                .par_iter()
+                .map(|vector| vector.iter().filter(|value| predicate(value)).count())
+                .sum()
+        })
+    }
+
+    #[inline]
+    /// Returns the number of elements matching the predicate in each sub-vector, counting in parallel.
+    ///
+    /// # Implementation details
+    /// This is the filtered analogue of [`Frontier::vector_sizes`], handy to
+    /// analyse how the work is distributed across the workers: the returned
+    /// vector has one entry per sub-vector, i.e. its length is [`Frontier::number_of_threads`].
+    ///
+    /// # Arguments
+    /// * `predicate`: P - Closure returning whether an element matches.
+    pub fn par_count_by<P>(&self, predicate: P) -> Vec<usize>
+    where
+        P: Fn(&T) -> bool + Sync + Send,
+    {
+        self.install(|| {
+            self.data
+                .par_iter()
+                .map(|vector| vector.iter().filter(|value| predicate(value)).count())
+                .collect()
+        })
+    }
+
+    #[inline]
+    /// Returns any element matching the predicate, searching in parallel.
+    ///
+    /// # Implementation details
+    /// This delegates to rayon's `find_any`, so the search stops as soon as
+    /// a match is found but, when more elements match, which one is returned
+    /// is not deterministic; use [`Frontier::par_position`] to find the first one.
+    ///
+    /// # Arguments
+    /// * `predicate`: P - Closure returning whether an element matches.
+    pub fn par_find_any<P>(&self, predicate: P) -> Option<&T>
+    where
+        P: Fn(&T) -> bool + Sync + Send,
+    {
+        self.par_iter().find_any(|value| predicate(value))
+    }
+
+    #[inline]
+    /// Maps every element and reduces the results in parallel.
+    ///
+    /// # Implementation details
+    /// This is equivalent to `par_iter().map(map).reduce(identity, op)`, and
+    /// runs within the frontier [`ThreadPool`], if any, and not within the
+    /// pool of the caller.
+    /// As with rayon's `reduce`, `op` must be associative and `identity`
+    /// may be called more than once.
+    ///
+    /// # Arguments
+    /// * `map`: M - Closure converting each element.
+    /// * `identity`: ID - Closure returning the identity of `op`.
+    /// * `op`: F - Associative closure combining two results.
+    pub fn par_reduce<U, M, ID, F>(&self, map: M, identity: ID, op: F) -> U
+    where
+        U: Send,
+        M: Fn(&T) -> U + Sync + Send,
+        ID: Fn() -> U + Sync + Send,
+        F: Fn(U, U) -> U + Sync + Send,
+    {
+        self.install(|| self.par_iter().map(map).reduce(identity, op))
+    }
+
+    #[inline]
+    /// Returns the element with the minimum key, or `None` if the frontier is empty.
+    ///
+    /// # Implementation details
+    /// This delegates to rayon's `min_by_key` over [`Frontier::par_iter`],
+    /// within the frontier [`ThreadPool`], if any.
+    /// As the parallel iterator preserves the [`Frontier::iter`] order, ties
+    /// are resolved as in `Iterator::min_by_key`, i.e. the first minimum is returned.
+    ///
+    /// # Arguments
+    /// * `f`: F - Closure returning the key of an element.
+    pub fn par_min_by_key<K, F>(&self, f: F) -> Option<&T>
+    where
+        K: Ord + Send,
+        F: Fn(&T) -> K + Sync + Send,
+    {
+        self.install(|| self.par_iter().min_by_key(|value| f(value)))
+    }
+
+    #[inline]
+    /// Returns the element with the maximum key, or `None` if the frontier is empty.
+    ///
+    /// # Implementation details
+    /// This delegates to rayon's `max_by_key` over [`Frontier::par_iter`],
+    /// within the frontier [`ThreadPool`], if any.
+    /// As the parallel iterator preserves the [`Frontier::iter`] order, ties
+    /// are resolved as in `Iterator::max_by_key`, i.e. the last maximum is returned.
+    ///
+    /// # Arguments
+    /// * `f`: F - Closure returning the key of an element.
+    pub fn par_max_by_key<K, F>(&self, f: F) -> Option<&T>
+    where
+        K: Ord + Send,
+        F: Fn(&T) -> K + Sync + Send,
+    {
+        self.install(|| self.par_iter().max_by_key(|value| f(value)))
+    }
+
+    #[inline]
+    /// Iter in parallel every `step`-th element, starting from the first one.
+    ///
+    /// # Implementation details
+    /// The elements are the ones at the global indices `0, step, 2 * step, ...`
+    /// of the [`Frontier::iter`] order, as with `iter().step_by(step)`, but the
+    /// iterator can be split anywhere, also inside a sub-vector: rayon
+    /// aligns each split to a multiple of `step`, so the stride is preserved
+    /// across the split boundaries.
+    /// Like the other indexed adaptors, this runs in the pool of the caller.
+    ///
+    /// # Arguments
+    /// * `step`: usize - Distance between two consecutive elements.
+    ///
+    /// # Panics
+    /// If `step` is zero.
+    pub fn par_step_by(&self, step: usize) -> impl IndexedParallelIterator<Item = &T> + '_ {
+        assert!(step != 0, "step must be non-zero");
+        self.par_iter().step_by(step)
+    }
+
+    #[inline]
+    /// Returns the index of the first element matching the predicate, searching in parallel.
+    ///
+    /// # Implementation details
+    /// The index refers to the global order of [`Frontier::iter`] and the
+    /// result is deterministic: when more elements match, the lowest index
+    /// is returned and not the first one found by any thread.
+    ///
+    /// # Arguments
+    /// * `predicate`: P - Closure returning whether an element matches.
+    pub fn par_position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(&T) -> bool + Sync + Send,
+    {
+        self.par_iter().position_first(predicate)
+    }
+
+    #[inline]
+    /// Iter the elements in parallel in slices of at most `size` contiguous elements.
+    ///
+    /// # Implementation details
+    /// The chunks are the same returned by [`Frontier::shard_chunks`], and
+    /// never cross the sub-vector boundaries.
+    ///
+    /// # Arguments
+    /// * `size`: usize - Maximum number of elements in each chunk.
+    ///
+    /// # Panics
+    /// If `size` is zero.
+    pub fn par_shard_chunks(&self, size: usize) -> impl ParallelIterator<Item = &[T]> + '_ {
+        assert!(size != 0, "chunk size must be non-zero");
+        self.par_iter_vectors()
+            .flat_map_iter(move |vector| vector.chunks(size))
+    }
+
     #[inline]
     /// Iter the sub-vectors in parallel.
     pub fn par_iter_vectors(&self) -> impl IndexedParallelIterator<Item = &Vec<T>> + '_ {
         self.data.par_iter()
     }
 
+    #[inline]
+    /// Iter the elements in parallel, with each task covering exactly one sub-vector.
+    ///
+    /// # Implementation details
+    /// Differently from [`Frontier::par_iter`], which may split a sub-vector
+    /// across tasks and merge the tails of adjacent ones, the iterator is only
+    /// split at the sub-vector boundaries, so each task only touches the data of a single thread.
+    /// This balances poorly when the sub-vectors have very different lengths.
+    pub fn par_iter_by_shard(&self) -> impl ParallelIterator<Item = &T> + '_ {
+        self.data
+            .par_iter()
+            .with_max_len(1)
+            .flat_map_iter(|vector| vector.iter())
+    }
+
+    #[inline]
+    /// Iter the sub-vectors in parallel, together with their index.
+    ///
+    /// # Implementation details
+    /// The index is the one of the thread the sub-vector is assigned to,
+    /// i.e. the same used by [`Frontier::vector_sizes`].
+    pub fn par_iter_vectors_enumerated(
+        &self,
+    ) -> impl IndexedParallelIterator<Item = (usize, &Vec<T>)> + '_ {
+        self.data.par_iter().enumerate()
+    }
+
+    #[inline]
+    /// Iter the sub-vectors in parallel, together with the global index of their first element.
+    ///
+    /// # Implementation details
+    /// The offsets are the cumulative lengths of the preceding sub-vectors,
+    /// computed once before iterating, so the global index of the `i`-th
+    /// element of a sub-vector is its offset plus `i`, matching the order of [`Frontier::iter`].
+    pub fn par_iter_vectors_with_offset(
+        &self,
+    ) -> impl IndexedParallelIterator<Item = (usize, &Vec<T>)> + '_ {
+        let offsets = self.cumulative_lens();
+        self.data
+            .par_iter()
+            .enumerate()
+            .map(move |(thread_id, vector)| (offsets[thread_id], vector))
+    }
+
     #[inline]
     /// Iter the sub-vectors in parallel and mutably.
     pub fn par_iter_vectors_mut(
@@ -263,9 +2019,70 @@ where
         self.data.par_iter_mut()
     }
 
+    #[inline]
+    /// Scans each sub-vector in parallel into a new frontier with the same layout.
+    ///
+    /// # Implementation details
+    /// Each sub-vector is scanned left-to-right starting from a fresh clone
+    /// of `init`, producing exactly one output element per input element.
+    /// The state does NOT carry across sub-vector boundaries: the output of
+    /// each element only depends on the elements preceding it in its own
+    /// sub-vector.
+    ///
+    /// # Arguments
+    /// * `init`: S - Initial state of the scan of each sub-vector.
+    /// * `f`: F - Closure updating the state and returning the output element.
+    pub fn par_scan_shards<S, U, F>(&self, init: S, f: F) -> Frontier<'static, U>
+    where
+        S: Clone + Send + Sync,
+        U: Send,
+        F: Fn(&mut S, &T) -> U + Sync + Send,
+    {
+        Frontier::from_shards(self.install(|| {
+            self.data
+                .par_iter()
+                .map(|vector| {
+                    let mut state = init.clone();
+                    vector
+                        .iter()
+                        .map(|value| f(&mut state, value))
+                        .collect::<Vec<U>>()
+                })
+                .collect::<Vec<Vec<U>>>()
+        }))
+    }
+
     #[inline]
     /// Iter and consume the sub-vectors in parallel.
     pub fn into_par_iter_vectors(self) -> impl IndexedParallelIterator<Item = Vec<T>> {
-        self.data.into_par_iter()
+        self.data.into_vec().into_par_iter()
+    }
+}
+
+impl<'a, U, E> Frontier<'a, Result<U, E>>
+where
+    U: Send,
+    E: Send,
+{
+    /// Converts a frontier of results into a result of a frontier.
+    ///
+    /// # Implementation details
+    /// Each sub-vector is collected in parallel, within the frontier
+    /// [`ThreadPool`] if any, so the returned frontier has the same number
+    /// of sub-vectors as this one and, when all the elements are `Ok`, the same layout.
+    /// This is the frontier analogue of `Iterator::collect::<Result<Vec<_>, _>>()`:
+    /// the sub-vector results are then checked sequentially, so when more
+    /// than one element is an `Err`, the first one in the [`Frontier::iter`] order is returned.
+    pub fn transpose(self) -> Result<Frontier<'static, U>, E> {
+        let threads = self.threads;
+        let data = self.data.into_vec();
+        let shards = install_in(threads, || {
+            data.into_par_iter()
+                .map(|vector| vector.into_iter().collect::<Result<Vec<U>, E>>())
+                .collect::<Vec<_>>()
+        });
+        Ok(Frontier::from_shards(
+            shards.into_iter().collect::<Result<Vec<Vec<U>>, E>>()?,
+        ))
     }
 }
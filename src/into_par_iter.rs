@@ -0,0 +1,179 @@
+use crate::prelude::*;
+use rayon::iter::plumbing::*;
+use std::collections::VecDeque;
+
+/// Parallel iterator moving the elements out of a frontier, see [`Frontier::into_par_iter`].
+pub struct IntoFrontierParIter<T> {
+    data: Vec<Vec<T>>,
+}
+
+impl<T> IntoFrontierParIter<T> {
+    pub(crate) fn new(data: Vec<Vec<T>>) -> Self {
+        IntoFrontierParIter { data }
+    }
+}
+
+impl<T: Send> ParallelIterator for IntoFrontierParIter<T> {
+    type Item = T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(IndexedParallelIterator::len(self))
+    }
+}
+
+impl<T: Send> IndexedParallelIterator for IntoFrontierParIter<T> {
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.data.iter().map(|v| v.len()).sum()
+    }
+
+    fn with_producer<CB>(mut self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        // The producer takes ownership of the elements, so the sub-vectors
+        // are emptied beforehand and only need to free their buffers.
+        let slices = self
+            .data
+            .iter_mut()
+            .map(|vector| unsafe {
+                let len = vector.len();
+                vector.set_len(0);
+                std::slice::from_raw_parts_mut(vector.as_mut_ptr(), len)
+            })
+            .collect::<VecDeque<_>>();
+        callback.callback(IntoFrontierProducer { slices })
+    }
+}
+
+/// Producer owning the elements of the slices, which are dropped if not consumed.
+struct IntoFrontierProducer<'data, T> {
+    slices: VecDeque<&'data mut [T]>,
+}
+
+impl<'data, T> Drop for IntoFrontierProducer<'data, T> {
+    fn drop(&mut self) {
+        drop_slices(&mut self.slices);
+    }
+}
+
+impl<'data, T: Send> Producer for IntoFrontierProducer<'data, T> {
+    type Item = T;
+    type IntoIter = IntoFrontierIter<'data, T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        IntoFrontierIter {
+            slices: core::mem::take(&mut self.slices),
+        }
+    }
+
+    fn split_at(mut self, index: usize) -> (Self, Self) {
+        let mut high = core::mem::take(&mut self.slices);
+        let mut low = VecDeque::new();
+        let mut remaining = index;
+        while remaining > 0 {
+            let slice = high.pop_front().unwrap();
+            if remaining >= slice.len() {
+                // the split happens after the whole slice
+                remaining -= slice.len();
+                low.push_back(slice);
+            } else {
+                // the split point is inside the slice
+                let (low_slice, high_slice) = slice.split_at_mut(remaining);
+                low.push_back(low_slice);
+                high.push_front(high_slice);
+                remaining = 0;
+            }
+        }
+        (
+            IntoFrontierProducer { slices: low },
+            IntoFrontierProducer { slices: high },
+        )
+    }
+}
+
+/// Sequential iterator moving the elements out of the slices, which are dropped if not consumed.
+struct IntoFrontierIter<'data, T> {
+    slices: VecDeque<&'data mut [T]>,
+}
+
+impl<'data, T> Drop for IntoFrontierIter<'data, T> {
+    fn drop(&mut self) {
+        drop_slices(&mut self.slices);
+    }
+}
+
+impl<'data, T> Iterator for IntoFrontierIter<'data, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let slice = self.slices.front_mut()?;
+            if let Some((first, rest)) = core::mem::take(slice).split_first_mut() {
+                *slice = rest;
+                // The element is moved out and will never be read again.
+                return Some(unsafe { core::ptr::read(first) });
+            }
+            self.slices.pop_front();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.slices.iter().map(|slice| slice.len()).sum();
+        (len, Some(len))
+    }
+}
+
+impl<'data, T> DoubleEndedIterator for IntoFrontierIter<'data, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let slice = self.slices.back_mut()?;
+            if let Some((last, rest)) = core::mem::take(slice).split_last_mut() {
+                *slice = rest;
+                // The element is moved out and will never be read again.
+                return Some(unsafe { core::ptr::read(last) });
+            }
+            self.slices.pop_back();
+        }
+    }
+}
+
+impl<'data, T> ExactSizeIterator for IntoFrontierIter<'data, T> {}
+
+/// Drops in place the elements of the slices that were not moved out.
+fn drop_slices<T>(slices: &mut VecDeque<&mut [T]>) {
+    slices
+        .drain(..)
+        .for_each(|slice| unsafe { core::ptr::drop_in_place(slice as *mut [T]) });
+}
+
+impl<'a, T> Frontier<'a, T>
+where
+    T: Send,
+{
+    #[inline]
+    /// Converts the frontier into a parallel iterator moving out its elements.
+    ///
+    /// # Implementation details
+    /// The iterator owns the sub-vectors and yields the elements by value in
+    /// the [`Frontier::iter`] order, without copying them into an intermediate vector.
+    /// It is an [`IndexedParallelIterator`], so it can be split at any index:
+    /// the split points fall on a sub-vector boundary or inside a sub-vector,
+    /// and each half moves out the elements of its own sub-vector slices.
+    pub fn into_par_iter(self) -> IntoFrontierParIter<T> {
+        IntoFrontierParIter::new(self.into())
+    }
+}
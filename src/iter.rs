@@ -15,6 +15,9 @@ pub struct FrontierIter<'a, T> {
     value_idx_end: usize,
 
     cumulative_lens: Arc<Vec<usize>>,
+
+    // minimum number of elements of each part when splitting
+    min_len: usize,
 }
 
 impl<'a, T> core::fmt::Debug for FrontierIter<'a, T> {
@@ -25,6 +28,7 @@ impl<'a, T> core::fmt::Debug for FrontierIter<'a, T> {
             .field("vec_idx_end", &self.vec_idx_end)
             .field("value_idx_end", &self.value_idx_end)
             .field("cumulative_lens", &self.cumulative_lens)
+            .field("min_len", &self.min_len)
             .finish()
     }
 }
@@ -40,26 +44,63 @@ impl<'a, T> FrontierIter<'a, T> {
             vec_idx_end: father.number_of_threads() - 1,
             value_idx_end: father.as_ref().last().unwrap().len(),
 
-            cumulative_lens: Arc::new(
-                father
-                    .as_ref()
-                    .iter()
-                    .map(|v| v.len())
-                    .scan(0, |acc, val| {
-                        let res = *acc;
-                        *acc += val;
-                        Some(res)
-                    })
-                    .collect::<Vec<_>>(),
-            ),
+            cumulative_lens: father.cumulative_lens(),
+
+            min_len: 1,
         }
     }
 
+    /// Set the minimum number of elements below which the iterator is not split.
+    pub fn with_min_len(mut self, min_len: usize) -> Self {
+        self.min_len = min_len.max(1);
+        self
+    }
+
     pub fn len(&self) -> usize {
         let start_idx = self.cumulative_lens[self.vec_idx_start] + self.value_idx_start;
         let end_idx = self.cumulative_lens[self.vec_idx_end] + self.value_idx_end;
         end_idx - start_idx
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Split the iterator at the provided global index, which must be within its range.
+    ///
+    /// Either half may be empty when splitting at one of the ends.
+    fn split_at_global_index(mut self, split_idx: usize) -> (Self, Self) {
+        // the last sub-vector starting at or before the split point, kept
+        // within the range of the iterator, so that the offset within it is
+        // not past its end even when some sub-vectors are empty
+        let vec_idx_mid = (self
+            .cumulative_lens
+            .partition_point(|&start| start <= split_idx)
+            - 1)
+        .clamp(self.vec_idx_start, self.vec_idx_end);
+        let value_idx_mid = split_idx - self.cumulative_lens[vec_idx_mid];
+
+        // high part
+        let new_iter = Self {
+            father: self.father,
+
+            vec_idx_start: vec_idx_mid,
+            value_idx_start: value_idx_mid,
+
+            vec_idx_end: self.vec_idx_end,
+            value_idx_end: self.value_idx_end,
+
+            cumulative_lens: self.cumulative_lens.clone(),
+
+            min_len: self.min_len,
+        };
+
+        // low part
+        self.vec_idx_end = vec_idx_mid;
+        self.value_idx_end = value_idx_mid;
+
+        (self, new_iter)
+    }
 }
 
 impl<'a, T> core::iter::ExactSizeIterator for FrontierIter<'a, T> {}
@@ -108,10 +149,6 @@ impl<'a, T> core::iter::DoubleEndedIterator for FrontierIter<'a, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
         loop {
             // if we finished the values
-            if self.vec_idx_end == 0 && self.value_idx_end == 0 {
-                return None;
-            }
-
             if self.vec_idx_start >= self.vec_idx_end && self.value_idx_start >= self.value_idx_end
             {
                 return None;
@@ -125,19 +162,19 @@ impl<'a, T> core::iter::DoubleEndedIterator for FrontierIter<'a, T> {
             self.value_idx_end = self.father.as_ref()[self.vec_idx_end].len();
         }
 
-        let result = &self.father.as_ref()[self.vec_idx_end][self.value_idx_end];
+        // the end is exclusive, so we have to move it before reading
         self.value_idx_end -= 1;
-        Some(result)
+        Some(&self.father.as_ref()[self.vec_idx_end][self.value_idx_end])
     }
 }
 
-impl<'a, T: Sync> UnindexedProducer for FrontierIter<'a, T> {
+impl<'a, T: Send + Sync> UnindexedProducer for FrontierIter<'a, T> {
     type Item = &'a T;
 
     /// Split the file in two approximately balanced streams
-    fn split(mut self) -> (Self, Option<Self>) {
-        // Check if it's reasonable to split
-        if self.len() < 2 {
+    fn split(self) -> (Self, Option<Self>) {
+        // Check if it's reasonable to split, i.e. if both halves reach the minimum length
+        if self.len() < 2 * self.min_len {
             return (self, None);
         }
 
@@ -157,59 +194,12 @@ impl<'a, T: Sync> UnindexedProducer for FrontierIter<'a, T> {
             self.father.len()
         );
 
-        match self.cumulative_lens.binary_search(&split_idx) {
-            // the split happens at the margin between two vectors
-            Ok(vec_idx_mid) => {
-                // high part
-                let new_iter = Self {
-                    father: self.father,
-
-                    vec_idx_start: vec_idx_mid,
-                    value_idx_start: 0,
-
-                    vec_idx_end: self.vec_idx_end,
-                    value_idx_end: self.value_idx_end,
+        let (low, high) = self.split_at_global_index(split_idx);
 
-                    cumulative_lens: self.cumulative_lens.clone(),
-                };
-
-                // low part
-                self.vec_idx_end = vec_idx_mid - 1;
-                self.value_idx_end = self.father.as_ref()[self.vec_idx_end].len();
-
-                // return the two halfs
-                debug_assert_ne!(self.len(), 0);
-                debug_assert_ne!(new_iter.len(), 0);
-                (self, Some(new_iter))
-            }
-            // the split point is inside a vector so gg ez
-            Err(vec_idx_mid) => {
-                let vec_idx_mid = vec_idx_mid - 1;
-                let value_idx_mid = split_idx - self.cumulative_lens[vec_idx_mid];
-
-                // high part
-                let new_iter = Self {
-                    father: self.father,
-
-                    vec_idx_start: vec_idx_mid,
-                    value_idx_start: value_idx_mid,
-
-                    vec_idx_end: self.vec_idx_end,
-                    value_idx_end: self.value_idx_end,
-
-                    cumulative_lens: self.cumulative_lens.clone(),
-                };
-
-                // low part
-                self.vec_idx_end = vec_idx_mid;
-                self.value_idx_end = value_idx_mid;
-
-                // return the two halfs
-                debug_assert_ne!(self.len(), 0);
-                debug_assert_ne!(new_iter.len(), 0);
-                (self, Some(new_iter))
-            }
-        }
+        // return the two halfs
+        debug_assert_ne!(low.len(), 0);
+        debug_assert_ne!(high.len(), 0);
+        (low, Some(high))
     }
 
     fn fold_with<F>(self, folder: F) -> F
@@ -220,7 +210,7 @@ impl<'a, T: Sync> UnindexedProducer for FrontierIter<'a, T> {
     }
 }
 
-impl<'a, T: Sync> Producer for FrontierIter<'a, T> {
+impl<'a, T: Send + Sync> Producer for FrontierIter<'a, T> {
     type Item = &'a T;
     type IntoIter = Self;
 
@@ -228,61 +218,12 @@ impl<'a, T: Sync> Producer for FrontierIter<'a, T> {
         self
     }
 
-    fn split_at(mut self, index: usize) -> (Self, Self) {
-        let start_idx = self.cumulative_lens[self.vec_idx_start] + self.value_idx_start;
-        let split_idx = index + start_idx;
-        match self.cumulative_lens.binary_search(&split_idx) {
-            // the split happens at the margin between two vectors
-            Ok(vec_idx_mid) => {
-                // high part
-                let new_iter = Self {
-                    father: self.father,
-
-                    vec_idx_start: vec_idx_mid,
-                    value_idx_start: 0,
-
-                    vec_idx_end: self.vec_idx_end,
-                    value_idx_end: self.value_idx_end,
-
-                    cumulative_lens: self.cumulative_lens.clone(),
-                };
-
-                // low part
-                self.vec_idx_end = vec_idx_mid - 1;
-                self.value_idx_end = self.father.as_ref()[self.vec_idx_end].len();
-
-                // return the two halfs
-                debug_assert_ne!(self.len(), 0);
-                debug_assert_ne!(new_iter.len(), 0);
-                (self, new_iter)
-            }
-            // the split point is inside a vector so gg ez
-            Err(vec_idx_mid) => {
-                let vec_idx_mid = vec_idx_mid - 1;
-                let value_idx_mid = split_idx - self.cumulative_lens[vec_idx_mid];
-
-                // high part
-                let new_iter = Self {
-                    father: self.father,
-
-                    vec_idx_start: vec_idx_mid,
-                    value_idx_start: value_idx_mid,
-
-                    vec_idx_end: self.vec_idx_end,
-                    value_idx_end: self.value_idx_end,
-
-                    cumulative_lens: self.cumulative_lens.clone(),
-                };
-
-                // low part
-                self.vec_idx_end = vec_idx_mid;
-                self.value_idx_end = value_idx_mid;
+    fn min_len(&self) -> usize {
+        self.min_len
+    }
 
-                // return the two halfs
-                debug_assert_ne!(self.len(), 0);
-                debug_assert_ne!(new_iter.len(), 0);
-                (self, new_iter)
-            }
-        }
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let start_idx = self.cumulative_lens[self.vec_idx_start] + self.value_idx_start;
+        self.split_at_global_index(start_idx + index)
     }
 }
@@ -1,11 +1,31 @@
+mod bfs;
+mod builder;
+mod drain;
+mod error;
 mod frontier;
+mod into_par_iter;
 mod iter;
 mod par_iter;
 mod par_iter_indexed;
+mod priority_frontier;
+#[cfg(feature = "rand")]
+mod random;
+mod seen_set;
+mod shards;
+mod summary;
+mod thread_local_frontier;
 
 pub mod prelude {
+    pub use crate::bfs::*;
+    pub use crate::builder::*;
+    pub use crate::drain::*;
+    pub use crate::error::*;
     pub use crate::frontier::*;
+    pub use crate::into_par_iter::*;
     pub use crate::iter::*;
     pub use crate::par_iter::*;
+    pub use crate::priority_frontier::*;
+    pub use crate::summary::*;
+    pub use crate::thread_local_frontier::*;
     pub use rayon::prelude::*;
 }
@@ -1,13 +1,21 @@
 use crate::prelude::*;
-use rayon::iter::plumbing::bridge_unindexed;
+use rayon::iter::plumbing::bridge;
 
 pub struct FrontierParIter<'a, T> {
     pub(crate) father: &'a Frontier<'a, T>,
+    // computed once, as rayon queries it repeatedly while splitting
+    pub(crate) len: usize,
+    // minimum number of elements of each task
+    pub(crate) min_len: usize,
 }
 
 impl<'a, T> FrontierParIter<'a, T> {
     pub fn new(father: &'a Frontier<T>) -> Self {
-        FrontierParIter { father }
+        FrontierParIter {
+            father,
+            len: father.len(),
+            min_len: 1,
+        }
     }
 }
 
@@ -18,10 +26,12 @@ impl<'a, T: Send + Sync> ParallelIterator for FrontierParIter<'a, T> {
     where
         C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
     {
-        bridge_unindexed(FrontierIter::new(self.father), consumer)
+        // as the length is known, rayon expects the indexed bridge to be used
+        let father = self.father;
+        father.install(|| bridge(self, consumer))
     }
 
     fn opt_len(&self) -> Option<usize> {
-        None
+        Some(self.len)
     }
 }
@@ -6,18 +6,20 @@ impl<'a, T: Send + Sync> IndexedParallelIterator for FrontierParIter<'a, T> {
     where
         C: Consumer<Self::Item>,
     {
-        bridge(self, consumer)
+        let father = self.father;
+        father.install(|| bridge(self, consumer))
     }
 
     fn len(&self) -> usize {
-        self.father.len()
+        self.len
     }
 
     fn with_producer<CB>(self, callback: CB) -> CB::Output
     where
         CB: ProducerCallback<Self::Item>,
     {
-        // Drain every item, and then the vector only needs to free its buffer.
-        callback.callback(self.father.iter())
+        // rayon does not require the callback to be `Send`, so it cannot be
+        // moved into the frontier pool and it runs in the pool of the caller
+        callback.callback(self.father.iter().with_min_len(self.min_len))
     }
 }
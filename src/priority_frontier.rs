@@ -0,0 +1,165 @@
+use std::collections::BinaryHeap;
+
+use crate::frontier::current_thread_index;
+use crate::prelude::*;
+use crate::shards::Shards;
+use rayon::ThreadPool;
+
+/// Priority queue supporting concurrent pushes through a shared reference.
+///
+/// # Implementation details
+/// Like [`Frontier`], the elements are split in one sub-heap per thread, and
+/// each thread only ever pushes to (or pops from) the sub-heap at its own index.
+/// Popping from a thread returns the maximum of its own sub-heap, while
+/// [`PriorityFrontier::drain_sorted_iter`] yields the elements in global order.
+#[derive(Clone, Debug)]
+pub struct PriorityFrontier<'a, T> {
+    data: Shards<BinaryHeap<T>>,
+    threads: Option<&'a ThreadPool>,
+}
+
+impl<'a, T: Ord> core::default::Default for PriorityFrontier<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Ord> PriorityFrontier<'a, T> {
+    #[inline]
+    /// Create new priority frontier object.
+    pub fn new() -> Self {
+        Self::from_heaps(
+            (0..Frontier::<T>::system_number_of_threads())
+                .map(|_| BinaryHeap::new())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[inline]
+    /// Create new priority frontier object for the provided [`ThreadPool`].
+    ///
+    /// # Arguments
+    /// * `thread_pool`: &ThreadPool - The thread pool the priority frontier is used from.
+    pub fn with_threads(thread_pool: &'a ThreadPool) -> Self {
+        let mut frontier = Self::from_heaps(
+            (0..thread_pool.current_num_threads().max(1))
+                .map(|_| BinaryHeap::new())
+                .collect::<Vec<_>>(),
+        );
+        frontier.threads = Some(thread_pool);
+        frontier
+    }
+
+    #[inline]
+    /// Create new priority frontier object owning the provided sub-heaps.
+    fn from_heaps(heaps: Vec<BinaryHeap<T>>) -> Self {
+        PriorityFrontier {
+            data: Shards::from(heaps),
+            threads: None,
+        }
+    }
+
+    #[inline]
+    /// Push value onto the sub-heap of the current thread.
+    ///
+    /// # Implementation details
+    /// As [`Frontier::push`], this does not require any synchronization,
+    /// and when called outside of a Rayon thread pool the value is pushed
+    /// onto the first sub-heap.
+    ///
+    /// # Arguments
+    /// * `value`: T - Object to be pushed onto of the priority frontier.
+    pub fn push(&self, value: T) {
+        let thread_id = current_thread_index(self.threads);
+        unsafe { (*self.data.shard_ptr(thread_id)).push(value) };
+    }
+
+    #[inline]
+    /// Pop the greatest element of the sub-heap of the current thread.
+    ///
+    /// # Implementation details
+    /// This method is thread-local: it returns `None` when the sub-heap of
+    /// the current thread is empty even if other sub-heaps are not, and the
+    /// returned element is not necessarily the greatest of the whole priority frontier.
+    pub fn pop(&self) -> Option<T> {
+        let thread_id = current_thread_index(self.threads);
+        unsafe { (*self.data.shard_ptr(thread_id)).pop() }
+    }
+
+    #[inline]
+    /// Returns number of the threads, i.e. sub-heaps, in the priority frontier.
+    pub fn number_of_threads(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    /// Returns total length of the priority frontier, i.e. the total number of elements in all sub-heaps.
+    pub fn len(&self) -> usize {
+        self.data.iter().map(|heap| heap.len()).sum()
+    }
+
+    #[inline]
+    /// Returns whether the priority frontier is empty.
+    pub fn is_empty(&self) -> bool {
+        self.data.iter().all(|heap| heap.is_empty())
+    }
+
+    #[inline]
+    /// Clear all the sub-heaps, retaining their capacity.
+    pub fn clear(&mut self) {
+        self.data.iter_mut().for_each(|heap| heap.clear());
+    }
+
+    #[inline]
+    /// Removes all the elements, yielding them from the greatest to the smallest.
+    ///
+    /// # Implementation details
+    /// The sub-heaps are merged lazily, by keeping the greatest element of
+    /// each of them in an additional heap, so each element costs
+    /// O(log n + log k) where k is the number of sub-heaps.
+    /// If the iterator is dropped before being exhausted, the remaining elements are dropped too.
+    pub fn drain_sorted_iter(&mut self) -> impl Iterator<Item = T> + '_ {
+        let heads = self
+            .data
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(thread_id, heap)| heap.pop().map(|value| (value, thread_id)))
+            .collect::<BinaryHeap<_>>();
+        DrainSorted {
+            heaps: &mut self.data,
+            heads,
+        }
+    }
+}
+
+/// Iterator merging the sub-heaps of a [`PriorityFrontier`], see [`PriorityFrontier::drain_sorted_iter`].
+struct DrainSorted<'b, T: Ord> {
+    heaps: &'b mut [BinaryHeap<T>],
+    /// Greatest element not yet yielded of each non-empty sub-heap, with the index of the sub-heap.
+    heads: BinaryHeap<(T, usize)>,
+}
+
+impl<'b, T: Ord> Drop for DrainSorted<'b, T> {
+    fn drop(&mut self) {
+        self.heaps.iter_mut().for_each(|heap| heap.clear());
+    }
+}
+
+impl<'b, T: Ord> Iterator for DrainSorted<'b, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let (value, thread_id) = self.heads.pop()?;
+        if let Some(next) = self.heaps[thread_id].pop() {
+            self.heads.push((next, thread_id));
+        }
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.heads.len() + self.heaps.iter().map(|heap| heap.len()).sum::<usize>();
+        (len, Some(len))
+    }
+}
+
+impl<'b, T: Ord> ExactSizeIterator for DrainSorted<'b, T> {}
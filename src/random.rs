@@ -0,0 +1,134 @@
+use crate::frontier::install_in;
+use crate::prelude::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+impl<'a, T> Frontier<'a, T>
+where
+    T: Clone + Send + Sync,
+{
+    /// Returns `k` elements sampled without replacement with probability proportional to `weight`.
+    ///
+    /// # Implementation details
+    /// The sampling follows the A-Res algorithm by Efraimidis and Spirakis:
+    /// each element is assigned the key `u^(1 / weight)`, with `u` drawn
+    /// uniformly in `(0, 1]`, and the `k` elements with the largest keys are returned.
+    /// The sub-vectors are processed in parallel, each with its own random
+    /// generator seeded from `rng`, so that the result only depends on the
+    /// state of `rng` and on the layout of the frontier.
+    /// Elements with a weight that is not strictly positive and finite are
+    /// never sampled, and when fewer than `k` elements can be sampled all of them are returned.
+    ///
+    /// # Arguments
+    /// * `k`: usize - Number of elements to sample.
+    /// * `weight`: W - Closure returning the weight of an element.
+    /// * `rng`: &mut R - Random generator used to seed the sampling.
+    pub fn par_weighted_sample<R, W>(&self, k: usize, weight: W, rng: &mut R) -> Vec<T>
+    where
+        R: Rng,
+        W: Fn(&T) -> f64 + Sync + Send,
+    {
+        let seeds = (0..self.number_of_threads())
+            .map(|_| rng.gen::<u64>())
+            .collect::<Vec<_>>();
+
+        let mut candidates = self.install(|| {
+            self.par_iter_vectors()
+                .zip(seeds)
+                .flat_map_iter(|(vector, seed)| {
+                    let mut rng = StdRng::seed_from_u64(seed);
+                    let mut candidates = vector
+                        .iter()
+                        .filter_map(|value| {
+                            let weight = weight(value);
+                            if weight > 0.0 && weight.is_finite() {
+                                // The logarithm of the A-Res key preserves its order.
+                                Some(((1.0 - rng.gen::<f64>()).ln() / weight, value))
+                            } else {
+                                None
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    select_largest(&mut candidates, k);
+                    candidates
+                })
+                .collect::<Vec<_>>()
+        });
+
+        select_largest(&mut candidates, k);
+        candidates
+            .into_iter()
+            .map(|(_, value)| value.clone())
+            .collect()
+    }
+}
+
+impl<'a, T> Frontier<'a, T> {
+    /// Shuffles the elements uniformly, preserving the size of each sub-vector.
+    ///
+    /// # Implementation details
+    /// The elements of all the sub-vectors are moved into a single vector,
+    /// shuffled with the Fisher-Yates algorithm and moved back, so every
+    /// permutation of the [`Frontier::iter`] order is equally likely and
+    /// elements can end up in any sub-vector.
+    /// This runs sequentially and requires a temporary copy of the elements;
+    /// see [`Frontier::par_shuffle`] for a cheaper but weaker alternative.
+    ///
+    /// # Arguments
+    /// * `rng`: &mut R - Random generator used for the shuffle.
+    pub fn shuffle<R>(&mut self, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+    {
+        let sizes = self.vector_sizes();
+        let mut values = self
+            .as_mut()
+            .iter_mut()
+            .flat_map(|vector| vector.drain(..))
+            .collect::<Vec<_>>();
+        values.shuffle(rng);
+        let mut values = values.into_iter();
+        self.as_mut()
+            .iter_mut()
+            .zip(sizes)
+            .for_each(|(vector, size)| vector.extend(values.by_ref().take(size)));
+    }
+
+    /// Shuffles each sub-vector independently and in parallel.
+    ///
+    /// # Implementation details
+    /// Each sub-vector is shuffled with its own random generator seeded
+    /// from `rng`, so the result only depends on the state of `rng`.
+    /// Differently from [`Frontier::shuffle`], the elements never move across
+    /// sub-vectors, so this is not a uniform shuffle of the whole frontier.
+    ///
+    /// # Arguments
+    /// * `rng`: &mut R - Random generator used to seed the shuffles.
+    pub fn par_shuffle<R>(&mut self, rng: &mut R)
+    where
+        T: Send,
+        R: Rng + ?Sized,
+    {
+        let seeds = (0..self.number_of_threads())
+            .map(|_| rng.gen::<u64>())
+            .collect::<Vec<_>>();
+        let threads = self.thread_pool();
+        let data = self.as_mut();
+        install_in(threads, || {
+            data.par_iter_mut()
+                .zip(seeds)
+                .for_each(|(vector, seed)| vector.shuffle(&mut StdRng::seed_from_u64(seed)))
+        });
+    }
+}
+
+/// Retains only the `k` candidates with the largest keys.
+fn select_largest<T>(candidates: &mut Vec<(f64, T)>, k: usize) {
+    if candidates.len() > k {
+        if k > 0 {
+            candidates.select_nth_unstable_by(k - 1, |(a, _), (b, _)| b.total_cmp(a));
+        }
+        candidates.truncate(k);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::prelude::*;
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::hash::{BuildHasher, Hash};
+use std::sync::{Mutex, OnceLock};
+
+/// Concurrent set of the elements pushed onto a frontier with `push_unique`.
+///
+/// # Implementation details
+/// The set is split into independently locked shards, selected by the hash
+/// of the element, so that concurrent insertions rarely contend on the same lock.
+/// The shards are only allocated on the first insertion.
+pub(crate) struct SeenSet<T> {
+    hasher: RandomState,
+    shards: OnceLock<Box<[Mutex<HashSet<T>>]>>,
+}
+
+impl<T> SeenSet<T> {
+    #[inline]
+    /// Create new empty set, without allocating its shards.
+    pub(crate) fn new() -> Self {
+        SeenSet {
+            hasher: RandomState::new(),
+            shards: OnceLock::new(),
+        }
+    }
+
+    #[inline]
+    /// Clears all shards, maintaining their reached capacity.
+    pub(crate) fn clear(&mut self) {
+        if let Some(shards) = self.shards.get_mut() {
+            shards
+                .iter_mut()
+                .for_each(|shard| shard.get_mut().unwrap().clear());
+        }
+    }
+}
+
+impl<T> SeenSet<T>
+where
+    T: Eq + Hash + Clone,
+{
+    #[inline]
+    /// Inserts a clone of the value if it is not already present, returning whether it was inserted.
+    pub(crate) fn insert(&self, value: &T) -> bool {
+        let shards = self.shards.get_or_init(|| {
+            (0..4 * Frontier::<T>::system_number_of_threads())
+                .map(|_| Mutex::new(HashSet::new()))
+                .collect()
+        });
+        let shard_id = (self.hasher.hash_one(value) % shards.len() as u64) as usize;
+        let mut shard = shards[shard_id].lock().unwrap();
+        if shard.contains(value) {
+            false
+        } else {
+            shard.insert(value.clone())
+        }
+    }
+}
+
+impl<T> Clone for SeenSet<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let shards = OnceLock::new();
+        if let Some(seen) = self.shards.get() {
+            let _ = shards.set(
+                seen.iter()
+                    .map(|shard| Mutex::new(shard.lock().unwrap().clone()))
+                    .collect(),
+            );
+        }
+        SeenSet {
+            hasher: self.hasher.clone(),
+            shards,
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::cell::UnsafeCell;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Sub-vectors (or other per-thread containers) of a frontier, each of which can be mutated through a shared reference.
+///
+/// # Implementation details
+/// Every sub-vector is wrapped in an [`UnsafeCell`], so that a thread can
+/// push to its own sub-vector through [`Shards::shard_ptr`] while other
+/// threads do the same on theirs, without ever creating a shared reference
+/// to memory that is being mutated.
+/// The sub-vectors can also be seen as a plain slice of vectors through
+/// `Deref`, which is only sound while no sub-vector is mutated through a raw pointer.
+/// The table of the cumulative lengths of the sub-vectors is cached, and
+/// marked as stale on any access that may mutate them.
+pub(crate) struct Shards<S> {
+    inner: Vec<UnsafeCell<S>>,
+    cumulative_lens: Mutex<Option<Arc<Vec<usize>>>>,
+    stale: AtomicBool,
+}
+
+// SAFETY: a shared `Shards` hands out shared references to the sub-vectors,
+// which requires `S: Sync`, and raw pointers to them, through which the
+// frontier moves values across threads, which requires `S: Send`.
+// The frontier guarantees that each sub-vector is only mutated through
+// `shard_ptr` by the single thread it is assigned to.
+unsafe impl<S: Send + Sync> Sync for Shards<S> {}
+
+impl<S> Shards<S> {
+    #[inline(always)]
+    /// Returns a pointer to the sub-vector with the given index.
+    ///
+    /// # Panics
+    /// If `index` is out of range.
+    pub(crate) fn shard_ptr(&self, index: usize) -> *mut S {
+        // only write when needed, so that concurrent pushes do not contend on the flag
+        if !self.stale.load(Ordering::Relaxed) {
+            self.stale.store(true, Ordering::Relaxed);
+        }
+        self.inner[index].get()
+    }
+
+    #[inline(always)]
+    /// Returns the number of sub-vectors.
+    ///
+    /// # Implementation details
+    /// This shadows the length of the slice seen through `Deref`, so that
+    /// it can be called while other threads push through [`Shards::shard_ptr`]
+    /// without creating a shared reference to their sub-vectors.
+    pub(crate) fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline]
+    /// Returns the offset of the first element of each sub-vector, reusing the cached table if still valid.
+    ///
+    /// # Arguments
+    /// * `len_of`: F - Closure returning the number of elements of a sub-vector.
+    pub(crate) fn cumulative_lens<F>(&self, len_of: F) -> Arc<Vec<usize>>
+    where
+        F: Fn(&S) -> usize,
+    {
+        let mut cache = self.cumulative_lens.lock().unwrap();
+        if self.stale.swap(false, Ordering::Relaxed) || cache.is_none() {
+            *cache = Some(Arc::new(
+                self.iter()
+                    .map(len_of)
+                    .scan(0, |acc, val| {
+                        let res = *acc;
+                        *acc += val;
+                        Some(res)
+                    })
+                    .collect::<Vec<_>>(),
+            ));
+        }
+        cache.clone().unwrap()
+    }
+
+    #[inline]
+    /// Returns the owned sub-vectors.
+    pub(crate) fn into_vec(self) -> Vec<S> {
+        self.inner.into_iter().map(UnsafeCell::into_inner).collect()
+    }
+}
+
+impl<S> Deref for Shards<S> {
+    type Target = [S];
+
+    #[inline(always)]
+    fn deref(&self) -> &[S] {
+        // SAFETY: `UnsafeCell<S>` has the same in-memory representation as `S`.
+        unsafe { &*(self.inner.as_slice() as *const [UnsafeCell<S>] as *const [S]) }
+    }
+}
+
+impl<S> DerefMut for Shards<S> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [S] {
+        *self.stale.get_mut() = true;
+        // SAFETY: `UnsafeCell<S>` has the same in-memory representation
+        // as `S`, and the mutable borrow excludes any other access.
+        unsafe { &mut *(self.inner.as_mut_slice() as *mut [UnsafeCell<S>] as *mut [S]) }
+    }
+}
+
+impl<S> From<Vec<S>> for Shards<S> {
+    fn from(data: Vec<S>) -> Self {
+        Shards {
+            inner: data.into_iter().map(UnsafeCell::new).collect(),
+            cumulative_lens: Mutex::new(None),
+            stale: AtomicBool::new(true),
+        }
+    }
+}
+
+impl<S> IntoIterator for Shards<S> {
+    type Item = S;
+    type IntoIter = std::vec::IntoIter<S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vec().into_iter()
+    }
+}
+
+impl<S> Default for Shards<S> {
+    fn default() -> Self {
+        Shards::from(Vec::new())
+    }
+}
+
+impl<S: Clone> Clone for Shards<S> {
+    fn clone(&self) -> Self {
+        Shards::from(self.to_vec())
+    }
+}
+
+impl<S: core::fmt::Debug> core::fmt::Debug for Shards<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
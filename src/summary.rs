@@ -0,0 +1,102 @@
+use crate::prelude::*;
+use core::fmt::Display;
+use core::iter::Sum;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Summary statistics of the elements of a frontier, see [`Frontier::par_summary`].
+pub struct Summary<T> {
+    /// Number of elements.
+    pub count: usize,
+    /// Smallest element, or `None` when there are no elements.
+    pub min: Option<T>,
+    /// Largest element, or `None` when there are no elements.
+    pub max: Option<T>,
+    /// Sum of the elements.
+    pub sum: T,
+}
+
+impl<T> Summary<T>
+where
+    T: Copy + Ord + Sum<T>,
+{
+    #[inline]
+    /// Returns the summary of no elements.
+    fn empty() -> Self {
+        Summary {
+            count: 0,
+            min: None,
+            max: None,
+            sum: core::iter::empty().sum(),
+        }
+    }
+
+    #[inline]
+    /// Returns the summary of the provided elements, computed in a single pass.
+    fn from_slice(values: &[T]) -> Self {
+        let mut min: Option<T> = None;
+        let mut max: Option<T> = None;
+        let sum = values
+            .iter()
+            .copied()
+            .inspect(|&value| {
+                min = Some(min.map_or(value, |min| min.min(value)));
+                max = Some(max.map_or(value, |max| max.max(value)));
+            })
+            .sum();
+        Summary {
+            count: values.len(),
+            min,
+            max,
+            sum,
+        }
+    }
+
+    #[inline]
+    /// Returns the summary of the union of the elements of the two summaries.
+    fn merge(self, other: Self) -> Self {
+        Summary {
+            count: self.count + other.count,
+            min: self.min.into_iter().chain(other.min).min(),
+            max: self.max.into_iter().chain(other.max).max(),
+            sum: [self.sum, other.sum].into_iter().sum(),
+        }
+    }
+}
+
+impl<T> Display for Summary<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "count: {}, min: ", self.count)?;
+        match &self.min {
+            Some(min) => write!(f, "{}", min)?,
+            None => write!(f, "-")?,
+        }
+        write!(f, ", max: ")?;
+        match &self.max {
+            Some(max) => write!(f, "{}", max)?,
+            None => write!(f, "-")?,
+        }
+        write!(f, ", sum: {}", self.sum)
+    }
+}
+
+impl<'a, T> Frontier<'a, T>
+where
+    T: Copy + Ord + Sum<T> + Send + Sync,
+{
+    /// Returns the count, minimum, maximum and sum of the elements of the frontier.
+    ///
+    /// # Implementation details
+    /// The statistics are computed in a single parallel pass: a partial
+    /// summary is computed for each sub-vector and the partial summaries
+    /// are then reduced into the summary of the whole frontier.
+    pub fn par_summary(&self) -> Summary<T> {
+        self.install(|| {
+            self.par_iter_vectors()
+                .map(|vector| Summary::from_slice(vector))
+                .reduce(Summary::empty, Summary::merge)
+        })
+    }
+}
@@ -0,0 +1,151 @@
+use std::cell::{RefCell, UnsafeCell};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Source of the unique identifiers of the thread-local frontiers.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Sub-vector registered by the current thread for each thread-local frontier, by identifier.
+    static REGISTRATIONS: RefCell<HashMap<usize, (usize, *mut ())>> = RefCell::new(HashMap::new());
+}
+
+/// Unordered vector supporting concurrent pushes from any registered thread.
+///
+/// # Implementation details
+/// Differently from [`Frontier`](crate::prelude::Frontier), which assigns the
+/// sub-vectors by rayon thread index, every thread calling
+/// [`ThreadLocalFrontier::register`] gets a new sub-vector of its own,
+/// recorded in a thread-local registry, so this can be used from rayon
+/// workers and plain `std::thread`s alike.
+/// Each sub-vector is boxed, so that its address does not change when
+/// other threads register, and is only ever pushed to by the thread that registered it.
+/// The registry keeps an entry for each frontier a thread registered to
+/// until the thread exits, as identifiers are never reused.
+/// The methods reading the elements take `&mut self`, so that no thread can
+/// push while the sub-vectors are read.
+pub struct ThreadLocalFrontier<T> {
+    id: usize,
+    // the boxes keep the sub-vectors in place when the outer vector grows
+    #[allow(clippy::vec_box)]
+    shards: Mutex<Vec<Box<UnsafeCell<Vec<T>>>>>,
+}
+
+// SAFETY: each sub-vector is only mutated by the thread that registered it,
+// while references to the sub-vectors are only handed out by the reading
+// methods, which borrow the frontier mutably and so cannot run concurrently with pushes.
+unsafe impl<T: Send + Sync> Sync for ThreadLocalFrontier<T> {}
+
+impl<T> core::fmt::Debug for ThreadLocalFrontier<T>
+where
+    T: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThreadLocalFrontier")
+            .field("id", &self.id)
+            .field("number_of_threads", &self.number_of_threads())
+            .finish()
+    }
+}
+
+impl<T> core::default::Default for ThreadLocalFrontier<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ThreadLocalFrontier<T> {
+    #[inline]
+    /// Create new thread-local frontier object, without any sub-vector.
+    pub fn new() -> Self {
+        ThreadLocalFrontier {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            shards: Mutex::new(Vec::new()),
+        }
+    }
+
+    #[inline]
+    /// Registers the current thread, returning the index of its sub-vector.
+    ///
+    /// # Implementation details
+    /// The first call from a thread creates a new sub-vector, while the
+    /// following ones return the index of the already registered one.
+    pub fn register(&self) -> usize {
+        REGISTRATIONS.with(|registrations| {
+            let mut registrations = registrations.borrow_mut();
+            if let Some((index, _)) = registrations.get(&self.id) {
+                return *index;
+            }
+            let mut shards = self.shards.lock().unwrap();
+            shards.push(Box::new(UnsafeCell::new(Vec::new())));
+            let index = shards.len() - 1;
+            registrations.insert(self.id, (index, shards[index].get() as *mut ()));
+            index
+        })
+    }
+
+    #[inline]
+    /// Push value onto the sub-vector of the current thread.
+    ///
+    /// # Arguments
+    /// * `value`: T - Object to be pushed onto of the frontier.
+    ///
+    /// # Panics
+    /// If the current thread did not call [`ThreadLocalFrontier::register`].
+    pub fn push(&self, value: T) {
+        let shard = REGISTRATIONS
+            .with(|registrations| registrations.borrow().get(&self.id).copied())
+            .map(|(_, shard)| shard as *mut Vec<T>)
+            .expect("the current thread is not registered to this frontier");
+        // SAFETY: the sub-vector was registered by the current thread, and
+        // it is alive as long as the frontier.
+        unsafe { (*shard).push(value) };
+    }
+
+    /// Iter the sub-vectors in registration order.
+    fn shards(&mut self) -> impl Iterator<Item = &Vec<T>> + '_ {
+        self.shards
+            .get_mut()
+            .unwrap()
+            .iter_mut()
+            .map(|shard| &*shard.get_mut())
+    }
+
+    #[inline]
+    /// Returns number of the registered threads, i.e. sub-vectors.
+    pub fn number_of_threads(&self) -> usize {
+        self.shards.lock().unwrap().len()
+    }
+
+    #[inline]
+    /// Returns total length of the frontier, i.e. the total number of elements in all sub-vectors.
+    pub fn len(&mut self) -> usize {
+        self.shards().map(|vector| vector.len()).sum()
+    }
+
+    #[inline]
+    /// Returns whether frontier is empty.
+    pub fn is_empty(&mut self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    /// Iter the elements sequentially, in the registration order of their sub-vectors.
+    ///
+    /// # Implementation details
+    /// Differently from [`Frontier`](crate::prelude::Frontier), this borrows
+    /// the frontier mutably, so no thread can push while the iterator is alive.
+    pub fn iter(&mut self) -> impl Iterator<Item = &T> + '_ {
+        self.shards().flat_map(|vector| vector.iter())
+    }
+
+    #[inline]
+    /// Returns all the elements in a single vector, in the order of [`ThreadLocalFrontier::iter`].
+    pub fn concat(&mut self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+}
@@ -0,0 +1,44 @@
+extern crate parallel_frontier;
+use parallel_frontier::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn test_bfs() {
+    // 0 -> 1 -> 3 -> 5
+    //  \-> 2 -/    \-> 6
+    // 4 is not reachable
+    let graph: Vec<Vec<usize>> = vec![
+        vec![1, 2],
+        vec![3],
+        vec![3, 0],
+        vec![5, 6],
+        vec![0],
+        vec![],
+        vec![3],
+    ];
+    let distances = (0..graph.len())
+        .map(|_| AtomicUsize::new(usize::MAX))
+        .collect::<Vec<_>>();
+    distances[0].store(0, Ordering::Relaxed);
+
+    let levels = bfs(0, |&node, next| {
+        let distance = distances[node].load(Ordering::Relaxed) + 1;
+        for &neighbour in &graph[node] {
+            if distances[neighbour]
+                .compare_exchange(usize::MAX, distance, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            {
+                next.push(neighbour);
+            }
+        }
+    });
+
+    assert_eq!(4, levels);
+    assert_eq!(
+        vec![0, 1, 1, 2, usize::MAX, 3, 3],
+        distances
+            .into_iter()
+            .map(AtomicUsize::into_inner)
+            .collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,1715 @@
+extern crate parallel_frontier;
+use parallel_frontier::prelude::*;
+use rayon::ThreadPoolBuilder;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+thread_local! {
+    static WORKER_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Returns a frontier with the provided sub-vectors.
+fn frontier_from_shards<T>(shards: Vec<Vec<T>>) -> Frontier<'static, T> {
+    let mut frontier = Frontier::with_index_fn(shards.len(), || 0);
+    frontier
+        .as_mut()
+        .iter_mut()
+        .zip(shards)
+        .for_each(|(vector, shard)| *vector = shard);
+    frontier
+}
+
+#[test]
+fn test_with_index_fn() {
+    let n_workers = 4;
+    let n = 1000;
+    let frontier = Frontier::with_index_fn(n_workers, || WORKER_ID.with(|id| id.get()));
+
+    std::thread::scope(|scope| {
+        for worker_id in 0..n_workers {
+            let frontier = &frontier;
+            scope.spawn(move || {
+                WORKER_ID.with(|id| id.set(worker_id));
+                for i in 0..n {
+                    frontier.push(i);
+                }
+                assert_eq!(frontier.pop(), Some(n - 1));
+            });
+        }
+    });
+
+    assert_eq!(n_workers, frontier.number_of_threads());
+    assert_eq!(vec![n - 1; n_workers], frontier.vector_sizes());
+}
+
+#[test]
+fn test_transpose_all_ok() {
+    let frontier: Frontier<Result<usize, String>> = Frontier::new();
+    (0..24).into_par_iter().for_each(|i| frontier.push(Ok(i)));
+
+    let transposed = frontier.clone().transpose().unwrap();
+
+    assert_eq!(frontier.vector_sizes(), transposed.vector_sizes());
+    assert_eq!(
+        frontier
+            .iter()
+            .cloned()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap(),
+        transposed.iter().copied().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_transpose_with_err() {
+    let frontier: Frontier<Result<usize, String>> = Frontier::new();
+    for i in 0..10 {
+        if i == 5 {
+            frontier.push(Err(format!("element {} is invalid", i)));
+        } else {
+            frontier.push(Ok(i));
+        }
+    }
+
+    assert_eq!(
+        frontier.transpose().unwrap_err(),
+        "element 5 is invalid".to_string()
+    );
+}
+
+#[test]
+fn test_transpose_returns_first_err() {
+    let pool = ThreadPoolBuilder::default().num_threads(4).build().unwrap();
+    let mut frontier: Frontier<Result<usize, String>> =
+        Frontier::builder().threads(&pool).num_shards(4).build();
+    frontier.fill_shards_from(|thread_id| {
+        (0..1000)
+            .map(|i| {
+                if (thread_id == 1 && i == 900) || (thread_id == 3 && i == 0) {
+                    Err(format!("element {} of shard {} is invalid", i, thread_id))
+                } else {
+                    Ok(i)
+                }
+            })
+            .collect()
+    });
+
+    assert_eq!(
+        frontier.iter().cloned().collect::<Result<Vec<_>, _>>(),
+        frontier
+            .clone()
+            .transpose()
+            .map(|frontier| frontier.concat())
+    );
+    assert_eq!(
+        "element 900 of shard 1 is invalid".to_string(),
+        frontier.transpose().unwrap_err()
+    );
+}
+
+#[test]
+fn test_bounded_try_push() {
+    let frontier = Frontier::with_bounded_capacity(3);
+
+    for i in 0..3 {
+        assert_eq!(Ok(()), frontier.try_push(i));
+    }
+    assert_eq!(Err(3), frontier.try_push(3));
+    assert_eq!(3, frontier.len());
+
+    // The unbounded push ignores the cap.
+    frontier.push(3);
+    assert_eq!(4, frontier.len());
+
+    assert_eq!(Some(3), frontier.pop());
+    assert_eq!(Some(2), frontier.pop());
+    assert_eq!(Ok(()), frontier.try_push(2));
+}
+
+#[test]
+fn test_unbounded_try_push() {
+    let frontier = Frontier::new();
+    for i in 0..100 {
+        assert_eq!(Ok(()), frontier.try_push(i));
+    }
+    assert_eq!(100, frontier.len());
+}
+
+#[test]
+fn test_par_scan_shards() {
+    let frontier = frontier_from_shards(vec![vec![1, 2, 3], vec![], vec![4, 5], vec![6]]);
+
+    let running_sums = frontier.par_scan_shards(0, |sum, value| {
+        *sum += value;
+        *sum
+    });
+
+    assert_eq!(frontier.vector_sizes(), running_sums.vector_sizes());
+    assert_eq!(
+        vec![1, 3, 6, 4, 9, 6],
+        running_sums.iter().copied().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_chunks_dividing_evenly() {
+    let frontier = frontier_from_shards(vec![vec![0, 1, 2], vec![3, 4], vec![5], vec![6, 7]]);
+    let expected = vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]];
+
+    let chunks = frontier
+        .chunks(4)
+        .map(|chunk| chunk.into_iter().copied().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(expected, chunks);
+
+    let par_chunks = frontier
+        .par_chunks(4)
+        .map(|chunk| chunk.into_iter().copied().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(expected, par_chunks);
+}
+
+#[test]
+fn test_chunks_with_short_last_chunk() {
+    let frontier = frontier_from_shards(vec![vec![0, 1, 2], vec![3, 4], vec![5], vec![6]]);
+    let expected = vec![vec![0, 1, 2, 3, 4], vec![5, 6]];
+
+    let chunks = frontier
+        .chunks(5)
+        .map(|chunk| chunk.into_iter().copied().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(expected, chunks);
+
+    let par_chunks = frontier
+        .par_chunks(5)
+        .map(|chunk| chunk.into_iter().copied().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(expected, par_chunks);
+}
+
+#[test]
+fn test_push_unique() {
+    let mut frontier = Frontier::new();
+
+    let pushed = (0..1000)
+        .into_par_iter()
+        .filter(|i| frontier.push_unique(i % 100))
+        .count();
+    assert_eq!(100, pushed);
+    assert_eq!(100, frontier.len());
+    assert_eq!((0..100).collect::<Vec<_>>(), frontier.to_sorted_vec());
+
+    // Clearing the frontier also resets the seen elements.
+    frontier.clear();
+    assert!(frontier.push_unique(0));
+    assert!(!frontier.push_unique(0));
+    assert_eq!(1, frontier.len());
+
+    // And so does draining it.
+    assert_eq!(vec![0], frontier.drain().collect::<Vec<_>>());
+    assert!(frontier.push_unique(0));
+    assert!(!frontier.push_unique(0));
+    assert_eq!(1, frontier.len());
+
+    assert_eq!(vec![0], frontier.drain_into_vec());
+    assert!(frontier.push_unique(0));
+    assert!(!frontier.push_unique(0));
+    assert_eq!(1, frontier.len());
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_par_weighted_sample() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let frontier =
+        frontier_from_shards(vec![vec![0, 1, 2], vec![3, 4, 5, 6], vec![], vec![7, 8, 9]]);
+    let mut rng = StdRng::seed_from_u64(42);
+    let weight = |value: &usize| if *value == 7 { 100.0 } else { 1.0 };
+
+    let mut counts = [0; 10];
+    for _ in 0..1000 {
+        let sample = frontier.par_weighted_sample(2, weight, &mut rng);
+        assert_eq!(2, sample.len());
+        assert_ne!(sample[0], sample[1]);
+        sample.into_iter().for_each(|value| counts[value] += 1);
+    }
+
+    // The element 7 is expected to be nearly always sampled.
+    assert!(counts[7] > 950, "{:?}", counts);
+    assert!(
+        counts.iter().all(|&count| count <= counts[7]),
+        "{:?}",
+        counts
+    );
+
+    // Elements with non-positive weight are never sampled.
+    let mut sample = frontier.par_weighted_sample(20, |value| (*value % 2) as f64, &mut rng);
+    sample.sort();
+    assert_eq!(vec![1, 3, 5, 7, 9], sample);
+
+    // The weights are computed within the thread pool of the frontier.
+    let pool = ThreadPoolBuilder::default().num_threads(3).build().unwrap();
+    let frontier = Frontier::with_threads(&pool, None);
+    pool.install(|| (0..100).into_par_iter().for_each(|i| frontier.push(i)));
+    let sample = frontier.par_weighted_sample(
+        10,
+        |_| {
+            assert_eq!(3, rayon::current_num_threads());
+            1.0
+        },
+        &mut rng,
+    );
+    assert_eq!(10, sample.len());
+}
+
+#[test]
+fn test_concat_into() {
+    let frontier = frontier_from_shards(vec![vec![0, 1, 2], vec![], vec![3, 4]]);
+    let mut out = Vec::with_capacity(100);
+    out.extend([7, 7, 7, 7, 7, 7, 7, 7]);
+
+    frontier.concat_into(&mut out);
+
+    assert_eq!(frontier.concat(), out);
+    assert!(out.capacity() >= 100);
+}
+
+#[test]
+fn test_concat_into_reused_buffer() {
+    let mut out = Vec::new();
+
+    let frontier = frontier_from_shards(vec![vec![0, 1, 2], vec![], vec![3, 4, 5, 6]]);
+    frontier.concat_into(&mut out);
+    assert_eq!(vec![0, 1, 2, 3, 4, 5, 6], out);
+    let capacity = out.capacity();
+
+    // the next level is shorter, so nothing of the previous one must be left
+    let frontier = frontier_from_shards(vec![vec![], vec![8], vec![9]]);
+    frontier.concat_into(&mut out);
+    assert_eq!(vec![8, 9], out);
+    assert_eq!(capacity, out.capacity());
+}
+
+#[test]
+fn test_position() {
+    let frontier = frontier_from_shards(vec![vec![5, 1], vec![], vec![2, 8], vec![3, 9, 8]]);
+
+    assert_eq!(Some(3), frontier.position(|&value| value == 8));
+    assert_eq!(Some(3), frontier.par_position(|&value| value == 8));
+
+    // The match lies in the last shard.
+    assert_eq!(Some(5), frontier.position(|&value| value == 9));
+    assert_eq!(Some(5), frontier.par_position(|&value| value == 9));
+
+    assert_eq!(None, frontier.position(|&value| value == 4));
+    assert_eq!(None, frontier.par_position(|&value| value == 4));
+}
+
+#[test]
+fn test_par_summary() {
+    let frontier = frontier_from_shards(vec![vec![5, 1], vec![], vec![12, 8], vec![3, 9, 8]]);
+    let values = frontier.concat();
+
+    let summary = frontier.par_summary();
+
+    assert_eq!(values.len(), summary.count);
+    assert_eq!(values.iter().copied().min(), summary.min);
+    assert_eq!(values.iter().copied().max(), summary.max);
+    assert_eq!(values.iter().copied().sum::<u64>(), summary.sum);
+    assert_eq!("count: 7, min: 1, max: 12, sum: 46", summary.to_string());
+
+    let empty = Frontier::<u64>::new().par_summary();
+    assert_eq!(0, empty.count);
+    assert_eq!(None, empty.min);
+    assert_eq!(None, empty.max);
+    assert_eq!(0, empty.sum);
+    assert_eq!("count: 0, min: -, max: -, sum: 0", empty.to_string());
+}
+
+#[test]
+fn test_split_off() {
+    let shards = vec![vec![0, 1, 2], vec![], vec![3, 4], vec![5]];
+    let values = (0..6).collect::<Vec<_>>();
+
+    // At zero, at a shard boundary, mid-shard and at the length.
+    for at in [0, 3, 4, 6] {
+        let mut frontier = frontier_from_shards(shards.clone());
+        let tail = frontier.split_off(at);
+
+        assert_eq!(frontier.number_of_threads(), tail.number_of_threads());
+        assert_eq!(at, frontier.len());
+        assert_eq!(values[..at], frontier.concat());
+        assert_eq!(values[at..], tail.concat());
+    }
+}
+
+#[test]
+fn test_split_off_reconstructs_iter() {
+    let shards = vec![
+        vec![],
+        vec![0, 1],
+        vec![],
+        vec![],
+        vec![2, 3, 4],
+        vec![5],
+        vec![],
+    ];
+
+    for at in 0..=6 {
+        let mut frontier = frontier_from_shards(shards.clone());
+        let original = frontier.iter().copied().collect::<Vec<_>>();
+
+        let tail = frontier.split_off(at);
+
+        assert_eq!(shards.len(), tail.number_of_threads());
+        assert_eq!(6 - at, tail.len());
+        let reconstructed = frontier
+            .iter()
+            .chain(tail.iter())
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(original, reconstructed, "at: {}", at);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_split_off_out_of_range() {
+    let mut frontier = frontier_from_shards(vec![vec![0, 1, 2], vec![3]]);
+    frontier.split_off(5);
+}
+
+#[test]
+fn test_map_into() {
+    let frontier: Frontier<u32> =
+        frontier_from_shards(vec![vec![0, 1, 2], vec![], vec![3, 4], vec![5]]);
+    let sizes = frontier.vector_sizes();
+    let values = frontier.concat();
+
+    let mapped: Frontier<u64> = frontier.map_into(|value| value as u64 * 1_000_000_000);
+
+    assert_eq!(sizes, mapped.vector_sizes());
+    assert_eq!(
+        values
+            .into_iter()
+            .map(|value| value as u64 * 1_000_000_000)
+            .collect::<Vec<_>>(),
+        mapped.concat()
+    );
+}
+
+#[test]
+fn test_sorted_vec_independent_of_threads() {
+    let small_pool = ThreadPoolBuilder::default().num_threads(2).build().unwrap();
+    let large_pool = ThreadPoolBuilder::default().num_threads(5).build().unwrap();
+    let small_frontier = Frontier::with_threads(&small_pool, None);
+    let large_frontier = Frontier::with_threads(&large_pool, None);
+
+    small_pool.install(|| {
+        (0..1000)
+            .into_par_iter()
+            .for_each(|i| small_frontier.push(i))
+    });
+    large_pool.install(|| {
+        (0..1000)
+            .into_par_iter()
+            .rev()
+            .for_each(|i| large_frontier.push(i))
+    });
+
+    assert_eq!(2, small_frontier.number_of_threads());
+    assert_eq!(5, large_frontier.number_of_threads());
+    assert_eq!(
+        (0..1000).collect::<Vec<_>>(),
+        small_frontier.to_sorted_vec()
+    );
+    assert_eq!(
+        small_frontier.to_sorted_vec(),
+        large_frontier.to_sorted_vec()
+    );
+    assert_eq!(
+        small_frontier.to_sorted_vec(),
+        large_frontier.into_sorted_vec()
+    );
+}
+
+#[test]
+fn test_capacity_distribution() {
+    for capacity in [0, 1, 2, 7, 100, 1001] {
+        let frontier = Frontier::<usize>::with_capacity(capacity);
+        assert!(frontier.shard_capacities().into_iter().sum::<usize>() >= capacity);
+
+        for n_threads in [1, 3, 4] {
+            let pool = ThreadPoolBuilder::default()
+                .num_threads(n_threads)
+                .build()
+                .unwrap();
+            let frontier = Frontier::<usize>::with_threads(&pool, Some(capacity));
+            assert_eq!(n_threads, frontier.number_of_threads());
+            assert!(frontier.shard_capacities().into_iter().sum::<usize>() >= capacity);
+        }
+    }
+}
+
+#[test]
+#[allow(clippy::mutable_key_type)]
+fn test_hash() {
+    use std::collections::HashSet;
+
+    let mut levels = HashSet::new();
+    assert!(levels.insert(frontier_from_shards(vec![vec![0, 1], vec![2]])));
+    // Same elements in the same order, with a different shard layout.
+    assert!(!levels.insert(frontier_from_shards(vec![vec![0], vec![1, 2]])));
+    // Same elements in a different order.
+    assert!(levels.insert(frontier_from_shards(vec![vec![2], vec![0, 1]])));
+    assert!(levels.insert(frontier_from_shards(vec![vec![], vec![]])));
+    assert_eq!(3, levels.len());
+}
+
+#[test]
+fn test_ordered_and_unordered_eq() {
+    let frontier = frontier_from_shards(vec![vec![0, 1], vec![2, 2]]);
+    let relayouted = frontier_from_shards(vec![vec![0], vec![1, 2, 2]]);
+    let reordered = frontier_from_shards(vec![vec![2, 1], vec![2, 0]]);
+    let different = frontier_from_shards(vec![vec![0, 1], vec![1, 2]]);
+
+    assert_eq!(frontier, relayouted);
+    assert!(frontier.unordered_eq(&relayouted));
+
+    assert_ne!(frontier, reordered);
+    assert!(frontier.unordered_eq(&reordered));
+
+    assert_ne!(frontier, different);
+    assert!(!frontier.unordered_eq(&different));
+}
+
+#[test]
+fn test_random_access() {
+    let frontier = frontier_from_shards(vec![vec![], vec![0, 1, 2], vec![], vec![3], vec![4, 5]]);
+
+    for (index, value) in frontier.iter().enumerate() {
+        assert_eq!(Some(value), frontier.get(index));
+        assert_eq!(*value, frontier[index]);
+    }
+    assert_eq!(None, frontier.get(frontier.len()));
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 6 but the index is 6")]
+fn test_index_out_of_range() {
+    let frontier = frontier_from_shards(vec![vec![], vec![0, 1, 2], vec![], vec![3], vec![4, 5]]);
+    let _ = frontier[6];
+}
+
+#[test]
+fn test_shard_chunks() {
+    let frontier = frontier_from_shards(vec![
+        vec![0, 1, 2, 3, 4],
+        vec![],
+        vec![5, 6],
+        vec![7, 8, 9, 10],
+    ]);
+    let expected: Vec<&[usize]> = vec![&[0, 1], &[2, 3], &[4], &[5, 6], &[7, 8], &[9, 10]];
+
+    assert_eq!(expected, frontier.shard_chunks(2).collect::<Vec<_>>());
+    assert_eq!(expected, frontier.par_shard_chunks(2).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_shards_mut() {
+    let mut frontier = Frontier::<usize>::with_index_fn(4, || 0);
+
+    for (thread_id, vector) in frontier.shards_mut() {
+        vector.reserve_exact(thread_id * 10);
+    }
+
+    for (thread_id, capacity) in frontier.shard_capacities().into_iter().enumerate() {
+        assert!(capacity >= thread_id * 10);
+    }
+    assert!(frontier.is_empty());
+}
+
+#[test]
+fn test_par_clear() {
+    let mut frontier = frontier_from_shards(vec![
+        (0..100).map(|i| i.to_string()).collect::<Vec<_>>(),
+        vec![],
+        (0..50).map(|i| i.to_string()).collect::<Vec<_>>(),
+    ]);
+    frontier.push_unique("0".to_string());
+    let capacities = frontier.shard_capacities();
+
+    frontier.par_clear();
+
+    assert!(frontier.is_empty());
+    assert_eq!(capacities, frontier.shard_capacities());
+    assert!(frontier.push_unique("0".to_string()));
+}
+
+#[test]
+fn test_builder() {
+    let pool = ThreadPoolBuilder::default().num_threads(3).build().unwrap();
+
+    let frontier = Frontier::<usize>::builder().build();
+    assert_eq!(
+        Frontier::<usize>::system_number_of_threads(),
+        frontier.number_of_threads()
+    );
+    assert!(frontier.thread_pool().is_none());
+
+    let frontier = Frontier::<usize>::builder().capacity(10).build();
+    assert!(frontier.shard_capacities().into_iter().sum::<usize>() >= 10);
+    assert!(frontier.thread_pool().is_none());
+
+    let frontier = Frontier::<usize>::builder()
+        .num_shards(5)
+        .capacity(12)
+        .build();
+    assert_eq!(5, frontier.number_of_threads());
+    assert!(frontier.shard_capacities().into_iter().sum::<usize>() >= 12);
+    assert!(frontier.thread_pool().is_none());
+
+    let frontier = Frontier::<usize>::builder().threads(&pool).build();
+    assert_eq!(3, frontier.number_of_threads());
+    assert!(std::ptr::eq(&pool, frontier.thread_pool().unwrap()));
+
+    // The explicit number of shards overrides the number of threads of the pool.
+    let frontier = Frontier::<usize>::builder()
+        .threads(&pool)
+        .num_shards(6)
+        .capacity(7)
+        .build();
+    assert_eq!(6, frontier.number_of_threads());
+    assert!(frontier.shard_capacities().into_iter().sum::<usize>() >= 7);
+    assert!(std::ptr::eq(&pool, frontier.thread_pool().unwrap()));
+
+    pool.install(|| (0..100).into_par_iter().for_each(|i| frontier.push(i)));
+    assert_eq!(100, frontier.len());
+
+    // `shards` is an alias of `num_shards`.
+    let frontier = Frontier::<usize>::builder()
+        .threads(&pool)
+        .shards(4)
+        .build();
+    assert_eq!(4, frontier.number_of_threads());
+}
+
+#[test]
+fn test_shrink_shards_to() {
+    let mut frontier = frontier_from_shards(vec![
+        Vec::with_capacity(100),
+        (0..50).collect::<Vec<_>>(),
+        Vec::with_capacity(2),
+    ]);
+
+    frontier.shrink_shards_to(30);
+
+    let capacities = frontier.shard_capacities();
+    assert!((10..100).contains(&capacities[0]));
+    assert!(capacities[1] >= 50);
+    assert!(capacities[2] >= 2);
+    assert!(capacities.iter().sum::<usize>() >= 30);
+    assert_eq!(50, frontier.len());
+}
+
+#[test]
+fn test_rebalance() {
+    let mut frontier =
+        frontier_from_shards(vec![vec![], (0..1003).collect::<Vec<_>>(), vec![], vec![]]);
+    let values = frontier.concat();
+
+    frontier.rebalance();
+
+    assert_eq!(vec![251, 251, 251, 250], frontier.vector_sizes());
+    assert_eq!(values, frontier.concat());
+}
+
+#[test]
+fn test_peek() {
+    let frontier = Frontier::with_index_fn(2, || WORKER_ID.with(|id| id.get()));
+    assert_eq!(None, unsafe { frontier.peek_last() });
+    assert_eq!(None, unsafe { frontier.peek_first() });
+
+    for i in 0..5 {
+        frontier.push(i);
+    }
+    assert_eq!(Some(&4), unsafe { frontier.peek_last() });
+    assert_eq!(Some(&0), unsafe { frontier.peek_first() });
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            WORKER_ID.with(|id| id.set(1));
+            // The shard of this thread is empty.
+            assert_eq!(None, unsafe { frontier.peek_last() });
+            assert_eq!(None, unsafe { frontier.peek_first() });
+            frontier.push(10);
+            assert_eq!(Some(&10), unsafe { frontier.peek_last() });
+            assert_eq!(Some(&10), unsafe { frontier.peek_first() });
+        });
+    });
+
+    assert_eq!(unsafe { frontier.peek_last() }.copied(), frontier.pop());
+}
+
+#[test]
+fn test_equal_frontiers_hash_equally() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let frontier = frontier_from_shards(vec![vec![0, 1, 2], vec![], vec![3]]);
+    let relayouted = frontier_from_shards(vec![vec![0], vec![1], vec![2, 3]]);
+    let mut rebalanced = frontier.clone();
+    rebalanced.rebalance();
+
+    assert_eq!(frontier, relayouted);
+    assert_eq!(hash_of(&frontier), hash_of(&relayouted));
+    assert_eq!(frontier, rebalanced);
+    assert_eq!(hash_of(&frontier), hash_of(&rebalanced));
+}
+
+#[test]
+fn test_par_extend_from() {
+    let mut frontier = frontier_from_shards(vec![vec![0, 1], vec![], vec![2]]);
+    let other = frontier_from_shards(vec![vec![3], vec![4, 5], vec![]]);
+
+    assert!(frontier.par_extend_from(other).is_ok());
+    assert_eq!(vec![3, 2, 1], frontier.vector_sizes());
+    assert_eq!(vec![0, 1, 3, 4, 5, 2], frontier.concat());
+
+    let mismatched = frontier_from_shards(vec![vec![6], vec![7]]);
+    assert!(frontier.par_extend_from(mismatched).is_err());
+    assert_eq!(6, frontier.len());
+}
+
+#[test]
+fn test_truncate() {
+    let shards = vec![vec![0, 1, 2], vec![], vec![3, 4], vec![5]];
+
+    for len in 0..8 {
+        let mut frontier = frontier_from_shards(shards.clone());
+        frontier.truncate(len);
+        assert_eq!(len.min(6), frontier.len());
+        assert_eq!((0..len.min(6)).collect::<Vec<_>>(), frontier.concat());
+    }
+
+    let mut frontier = frontier_from_shards(shards);
+    frontier.truncate_shard(0, 1);
+    frontier.truncate_shard(2, 5);
+    assert_eq!(vec![0, 3, 4, 5], frontier.concat());
+}
+
+#[test]
+fn test_pop_shard_and_swap_remove() {
+    let mut frontier = frontier_from_shards(vec![vec![0, 1, 2], vec![], vec![3, 4, 5]]);
+
+    assert_eq!(Some(5), frontier.pop_shard(2));
+    assert_eq!(None, frontier.pop_shard(1));
+    assert_eq!(None, frontier.pop_shard(3));
+
+    assert_eq!(Some(0), frontier.swap_remove(0, 0));
+    assert_eq!(vec![2, 1, 3, 4], frontier.concat());
+    assert_eq!(None, frontier.swap_remove(0, 2));
+    assert_eq!(None, frontier.swap_remove(1, 0));
+    assert_eq!(None, frontier.swap_remove(3, 0));
+    assert_eq!(4, frontier.len());
+}
+
+#[test]
+fn test_clone_empty() {
+    let pool = ThreadPoolBuilder::default().num_threads(3).build().unwrap();
+    let frontier = Frontier::with_threads(&pool, Some(30));
+    pool.install(|| (0..100).into_par_iter().for_each(|i| frontier.push(i)));
+
+    let empty = frontier.clone_empty();
+
+    assert_eq!(frontier.number_of_threads(), empty.number_of_threads());
+    assert_eq!(0, empty.len());
+    assert!(std::ptr::eq(&pool, empty.thread_pool().unwrap()));
+    for (capacity, empty_capacity) in frontier
+        .shard_capacities()
+        .into_iter()
+        .zip(empty.shard_capacities())
+    {
+        assert!(empty_capacity >= capacity);
+    }
+}
+
+#[test]
+fn test_first_last() {
+    let frontier = frontier_from_shards(vec![vec![], vec![1, 2], vec![], vec![3], vec![]]);
+    assert_eq!(Some(&1), frontier.first());
+    assert_eq!(Some(&3), frontier.last());
+
+    let frontier = frontier_from_shards::<i32>(vec![vec![], vec![], vec![]]);
+    assert_eq!(None, frontier.first());
+    assert_eq!(None, frontier.last());
+}
+
+#[test]
+fn test_reverse() {
+    let mut frontier =
+        frontier_from_shards(vec![vec![], vec![1, 2, 3], vec![], vec![4, 5], vec![]]);
+    let reversed = frontier.iter().rev().copied().collect::<Vec<_>>();
+    assert_eq!(vec![5, 4, 3, 2, 1], reversed);
+
+    frontier.reverse();
+
+    assert_eq!(reversed, frontier.iter().copied().collect::<Vec<_>>());
+    assert_eq!(vec![0, 2, 0, 3, 0], frontier.vector_sizes());
+}
+
+#[test]
+fn test_iter_double_ended() {
+    let frontier = frontier_from_shards(vec![vec![1, 2], vec![], vec![3, 4]]);
+    let mut iter = frontier.iter();
+    assert_eq!(Some(&4), iter.next_back());
+    assert_eq!(Some(&1), iter.next());
+    assert_eq!(Some(&3), iter.next_back());
+    assert_eq!(Some(&2), iter.next_back());
+    assert_eq!(None, iter.next_back());
+    assert_eq!(None, iter.next());
+}
+
+#[test]
+fn test_debug_and_display() {
+    let frontier = frontier_from_shards(vec![vec![1, 2], vec![], vec![3]]);
+    assert_eq!(
+        "Frontier { shards: 3, len: 3, sizes: [2, 0, 1] }",
+        format!("{:?}", frontier)
+    );
+    assert!(format!("{:#?}", frontier).contains("data"));
+    assert_eq!("[1, 2, 3]", frontier.to_string());
+
+    let frontier = frontier_from_shards(vec![(0..5).collect(), (5..20).collect()]);
+    assert_eq!("[0, 1, 2, 3, 4, 5, 6, 7, ...]", frontier.to_string());
+
+    let frontier = frontier_from_shards::<i32>(vec![vec![]]);
+    assert_eq!("[]", frontier.to_string());
+}
+
+#[test]
+fn test_par_for_each_init() {
+    let frontier = frontier_from_shards(vec![(0..10).collect(), vec![], (10..100).collect()]);
+    let inits = AtomicUsize::new(0);
+    let sum = AtomicUsize::new(0);
+
+    frontier.par_for_each_init(
+        || {
+            inits.fetch_add(1, Ordering::Relaxed);
+            Vec::new()
+        },
+        |buffer: &mut Vec<usize>, value| {
+            buffer.push(*value);
+            sum.fetch_add(*value, Ordering::Relaxed);
+        },
+    );
+
+    assert_eq!((0..100).sum::<usize>(), sum.into_inner());
+    let inits = inits.into_inner();
+    assert!(
+        inits >= 1 && inits <= frontier.number_of_threads(),
+        "{}",
+        inits
+    );
+}
+
+#[test]
+fn test_extract_if() {
+    let mut frontier = frontier_from_shards(vec![(0..5).collect(), vec![], (5..10).collect()]);
+
+    let extracted = frontier
+        .extract_if(|value| value % 2 == 0)
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec![0, 2, 4, 6, 8], extracted);
+    assert_eq!(
+        vec![1, 3, 5, 7, 9],
+        frontier.iter().copied().collect::<Vec<_>>()
+    );
+    assert_eq!(vec![2, 0, 3], frontier.vector_sizes());
+}
+
+#[test]
+fn test_extract_if_dropped_early() {
+    let mut frontier = frontier_from_shards(vec![(0..5).collect(), (5..10).collect()]);
+
+    let extracted = frontier
+        .extract_if(|value| value % 2 == 0)
+        .take(2)
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec![0, 2], extracted);
+    assert_eq!(
+        vec![1, 3, 4, 5, 6, 7, 8, 9],
+        frontier.iter().copied().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_shard_slices() {
+    let frontier = frontier_from_shards(vec![vec![1, 2], vec![], vec![3], vec![]]);
+    let slices = frontier.shard_slices();
+    assert_eq!(vec![&[1, 2][..], &[3][..]], slices);
+    assert_eq!(
+        frontier.len(),
+        slices.iter().map(|slice| slice.len()).sum::<usize>()
+    );
+}
+
+#[test]
+fn test_par_extend() {
+    let pool = ThreadPoolBuilder::default().num_threads(3).build().unwrap();
+    let mut frontier = Frontier::with_threads(&pool, None);
+
+    frontier.par_extend((0..100_000).into_par_iter());
+
+    assert_eq!(100_000, frontier.len());
+    assert_eq!(
+        (0..100_000).collect::<Vec<_>>(),
+        frontier.clone().into_sorted_vec()
+    );
+
+    frontier.par_extend(vec![100_000, 100_001]);
+    assert_eq!(100_002, frontier.len());
+}
+
+#[test]
+fn test_concurrent_push_and_pop() {
+    // kept small so that it also runs under `cargo +nightly miri test`
+    let n_workers = 4;
+    let n = if cfg!(miri) { 50 } else { 10_000 };
+    let frontier = Frontier::with_index_fn(n_workers, || WORKER_ID.with(|id| id.get()));
+
+    std::thread::scope(|scope| {
+        for worker_id in 0..n_workers {
+            let frontier = &frontier;
+            scope.spawn(move || {
+                WORKER_ID.with(|id| id.set(worker_id));
+                for i in 0..n {
+                    frontier.push(i);
+                    assert_eq!(Some(&i), unsafe { frontier.peek_last() });
+                    assert_eq!(Ok(()), frontier.try_push(i));
+                    assert_eq!(Some(i), frontier.pop());
+                }
+            });
+        }
+    });
+
+    assert_eq!(vec![n; n_workers], frontier.vector_sizes());
+    let mut expected = (0..n).flat_map(|i| vec![i; n_workers]).collect::<Vec<_>>();
+    expected.sort_unstable();
+    assert_eq!(expected, frontier.into_sorted_vec());
+}
+
+#[test]
+fn test_extend_on_thread() {
+    let frontier = Frontier::with_index_fn(4, || 0);
+    unsafe { frontier.extend_on_thread(0..100, 2) };
+    frontier.push(100);
+
+    assert_eq!(vec![1, 0, 100, 0], frontier.vector_sizes());
+    assert_eq!((0..100).collect::<Vec<_>>(), frontier.as_ref()[2]);
+}
+
+#[test]
+fn test_par_iter_vectors_enumerated() {
+    let frontier = frontier_from_shards(vec![vec![1, 2], vec![], vec![3, 4, 5]]);
+    let mut sizes = vec![0; frontier.number_of_threads()];
+    for (thread_id, len) in frontier
+        .par_iter_vectors_enumerated()
+        .map(|(thread_id, vector)| (thread_id, vector.len()))
+        .collect::<Vec<_>>()
+    {
+        sizes[thread_id] = len;
+    }
+    assert_eq!(frontier.vector_sizes(), sizes);
+}
+
+#[test]
+fn test_par_reduce() {
+    let pool = ThreadPoolBuilder::default().num_threads(3).build().unwrap();
+    let frontier = Frontier::with_threads(&pool, None);
+    pool.install(|| {
+        (0..1000_usize)
+            .into_par_iter()
+            .for_each(|i| frontier.push(i))
+    });
+
+    let outside_pool = AtomicUsize::new(0);
+    let sum = frontier.par_reduce(
+        |value| {
+            if pool.current_thread_index().is_none() {
+                outside_pool.fetch_add(1, Ordering::Relaxed);
+            }
+            *value
+        },
+        || 0,
+        |a, b| a + b,
+    );
+
+    assert_eq!((0..1000).sum::<usize>(), sum);
+    assert_eq!(0, outside_pool.into_inner());
+}
+
+#[test]
+fn test_retain_mut() {
+    let shards = vec![vec![1, 2, 3], vec![], vec![1, 5]];
+    let mut frontier = frontier_from_shards(shards.clone());
+    let capacities = frontier.shard_capacities();
+
+    frontier.retain_mut(|value| {
+        *value -= 1;
+        *value != 0
+    });
+
+    assert_eq!(vec![1, 2, 4], frontier.iter().copied().collect::<Vec<_>>());
+    assert_eq!(capacities, frontier.shard_capacities());
+
+    let mut frontier = frontier_from_shards(shards);
+    frontier.par_retain_mut(|value| {
+        *value -= 1;
+        *value != 0
+    });
+
+    assert_eq!(3, frontier.len());
+    assert_eq!(vec![1, 2, 4], frontier.iter().copied().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_par_find_any() {
+    let frontier = frontier_from_shards(vec![vec![5, 1], vec![], vec![2, 8], vec![3, 9, 8]]);
+
+    assert_eq!(Some(&8), frontier.par_find_any(|&value| value == 8));
+    let even = frontier.par_find_any(|&value| value % 2 == 0).unwrap();
+    assert!(*even == 2 || *even == 8);
+    assert_eq!(None, frontier.par_find_any(|&value| value == 4));
+
+    let empty = frontier_from_shards::<i32>(vec![vec![], vec![]]);
+    assert_eq!(None, empty.position(|_| true));
+    assert_eq!(None, empty.par_find_any(|_| true));
+}
+
+#[test]
+fn test_par_iter_by_shard() {
+    // each sub-vector holds a disjoint range, so a value identifies its sub-vector
+    let frontier = frontier_from_shards(vec![(0..100).collect(), vec![], (100..300).collect()]);
+
+    let mut tasks = frontier
+        .par_iter_by_shard()
+        .fold(Vec::new, |mut task, value| {
+            task.push(*value);
+            task
+        })
+        .filter(|task| !task.is_empty())
+        .collect::<Vec<Vec<usize>>>();
+
+    for task in &tasks {
+        let first_shard = task[0] >= 100;
+        assert!(task.iter().all(|value| (*value >= 100) == first_shard));
+    }
+    let mut values = tasks.drain(..).flatten().collect::<Vec<_>>();
+    values.sort_unstable();
+    assert_eq!((0..300).collect::<Vec<_>>(), values);
+}
+
+#[test]
+fn test_iter_after_mutation() {
+    let mut frontier = Frontier::with_index_fn(1024, || 0);
+    frontier.push(1);
+
+    // repeatedly iterating an unchanged frontier reuses the cumulative lengths
+    for _ in 0..1000 {
+        assert_eq!(vec![1], frontier.iter().copied().collect::<Vec<_>>());
+    }
+
+    // pushing through a shared reference invalidates them
+    frontier.push(2);
+    assert_eq!(vec![1, 2], frontier.iter().copied().collect::<Vec<_>>());
+
+    // and so does any mutable access to the sub-vectors
+    frontier.as_mut()[512].push(3);
+    assert_eq!(vec![1, 2, 3], frontier.iter().copied().collect::<Vec<_>>());
+    assert_eq!(
+        vec![1, 2, 3],
+        frontier.par_iter().copied().collect::<Vec<_>>()
+    );
+    frontier.clear();
+    assert_eq!(0, frontier.iter().count());
+}
+
+#[test]
+fn test_iter_enumerate_shard() {
+    let frontier = frontier_from_shards(vec![vec![1, 2], vec![], vec![3, 4, 5], vec![6]]);
+
+    let mut counts = vec![0; frontier.number_of_threads()];
+    for (thread_id, value) in frontier.iter_enumerate_shard() {
+        assert!(frontier.as_ref()[thread_id].contains(value));
+        counts[thread_id] += 1;
+    }
+    assert_eq!(frontier.vector_sizes(), counts);
+    assert_eq!(
+        frontier.iter().collect::<Vec<_>>(),
+        frontier
+            .iter_enumerate_shard()
+            .map(|(_, value)| value)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_drain() {
+    let mut frontier = frontier_from_shards(vec![vec![1, 2], vec![], vec![3, 4, 5]]);
+    let capacities = frontier.shard_capacities();
+
+    assert_eq!(vec![1, 2, 3, 4, 5], frontier.drain().collect::<Vec<_>>());
+    assert!(frontier.is_empty());
+    assert_eq!(capacities, frontier.shard_capacities());
+}
+
+#[test]
+fn test_drain_double_ended() {
+    let mut frontier = frontier_from_shards(vec![vec![1, 2], vec![], vec![3, 4, 5], vec![6]]);
+
+    let mut drain = frontier.drain();
+    assert_eq!(6, drain.len());
+    assert_eq!(Some(1), drain.next());
+    assert_eq!(Some(6), drain.next_back());
+    assert_eq!(Some(5), drain.next_back());
+    assert_eq!(Some(2), drain.next());
+    // the cursors meet in the third sub-vector
+    assert_eq!(Some(3), drain.next());
+    assert_eq!(Some(4), drain.next_back());
+    assert_eq!(0, drain.len());
+    assert_eq!(None, drain.next());
+    assert_eq!(None, drain.next_back());
+    drop(drain);
+    assert!(frontier.is_empty());
+
+    let mut frontier = frontier_from_shards(vec![vec![1, 2], vec![3], vec![4, 5]]);
+    let mut drain = frontier.drain();
+    assert_eq!(Some(5), drain.next_back());
+    drop(drain);
+    assert!(frontier.is_empty());
+    assert_eq!(vec![0, 0, 0], frontier.vector_sizes());
+}
+
+#[test]
+fn test_as_slice() {
+    let frontier = Frontier::new();
+    (0..10).for_each(|i| frontier.push(i));
+    assert_eq!(Some(&(0..10).collect::<Vec<_>>()[..]), frontier.as_slice());
+
+    let frontier = frontier_from_shards(vec![vec![], vec![1, 2], vec![]]);
+    assert_eq!(Some(&[1, 2][..]), frontier.as_slice());
+
+    let frontier = frontier_from_shards(vec![vec![1], vec![], vec![2]]);
+    assert_eq!(None, frontier.as_slice());
+
+    let frontier = frontier_from_shards::<i32>(vec![vec![], vec![]]);
+    assert_eq!(None, frontier.as_slice());
+}
+
+#[test]
+fn test_unordered_eq_across_thread_counts() {
+    let two_threads = ThreadPoolBuilder::default().num_threads(2).build().unwrap();
+    let three_threads = ThreadPoolBuilder::default().num_threads(3).build().unwrap();
+    let two = Frontier::with_threads(&two_threads, None);
+    let mut three = Frontier::with_threads(&three_threads, None);
+    two_threads.install(|| (0..1000).into_par_iter().for_each(|i| two.push(i)));
+    three_threads.install(|| (0..1000).into_par_iter().for_each(|i| three.push(i)));
+    // make sure the orders differ, whatever the scheduling was
+    three.rebalance();
+    three.reverse();
+
+    assert_ne!(two, three);
+    assert!(two.unordered_eq(&three));
+}
+
+#[test]
+fn test_level_lengths() {
+    let mut frontier = Frontier::new();
+    assert!(frontier.level_lengths().is_empty());
+
+    frontier.push(0);
+    frontier.mark_level();
+    (1..4).into_par_iter().for_each(|i| frontier.push(i));
+    frontier.mark_level();
+    frontier.mark_level();
+    (4..9).into_par_iter().for_each(|i| frontier.push(i));
+    frontier.mark_level();
+    frontier.push(9);
+
+    assert_eq!(vec![1, 3, 0, 5], frontier.level_lengths());
+
+    frontier.clear();
+    assert!(frontier.level_lengths().is_empty());
+}
+
+#[test]
+fn test_level_lengths_reset() {
+    let mut frontier = frontier_from_shards(vec![vec![0, 1], vec![2]]);
+    frontier.mark_level();
+    frontier.push(3);
+    frontier.mark_level();
+
+    frontier.par_clear();
+    assert!(frontier.level_lengths().is_empty());
+    frontier.push(4);
+    frontier.mark_level();
+    assert_eq!(vec![1], frontier.level_lengths());
+
+    frontier.drain().for_each(drop);
+    assert!(frontier.level_lengths().is_empty());
+
+    frontier.push(5);
+    frontier.mark_level();
+    frontier.drain_into_vec();
+    assert!(frontier.level_lengths().is_empty());
+
+    // the levels ending past the new length are dropped
+    frontier.fill_shards_from(|thread_id| vec![thread_id; 3]);
+    frontier.mark_level();
+    frontier.push(6);
+    frontier.mark_level();
+    frontier.truncate(6);
+    assert_eq!(vec![6], frontier.level_lengths());
+    frontier.truncate(2);
+    assert!(frontier.level_lengths().is_empty());
+}
+
+#[test]
+fn test_reserve_shard() {
+    let mut frontier = frontier_from_shards::<usize>(vec![vec![], vec![], vec![]]);
+    frontier.reserve_shard(1, 1000);
+    frontier.as_mut()[1].extend(0..10);
+
+    assert!(frontier.shard_capacities()[1] >= 1000);
+    assert_eq!(0, frontier.shard_capacities()[0]);
+    assert_eq!(0, frontier.shard_capacities()[2]);
+    assert_eq!(10, frontier.shard_len(1));
+    assert_eq!(0, frontier.shard_len(2));
+}
+
+#[test]
+#[should_panic(expected = "the sub-vector index is 3 but the frontier has 3 sub-vectors")]
+fn test_reserve_shard_out_of_range() {
+    let mut frontier = frontier_from_shards::<usize>(vec![vec![], vec![], vec![]]);
+    frontier.reserve_shard(3, 1);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_shuffle() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let shards: Vec<Vec<usize>> = vec![(0..300).collect(), vec![], (300..1000).collect()];
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let mut frontier = frontier_from_shards(shards.clone());
+    frontier.shuffle(&mut rng);
+    assert_eq!(vec![300, 0, 700], frontier.vector_sizes());
+    assert_ne!((0..1000).collect::<Vec<_>>(), frontier.concat());
+    assert_eq!((0..1000).collect::<Vec<_>>(), frontier.to_sorted_vec());
+    // a uniform shuffle moves elements across the sub-vectors
+    assert!(frontier.as_ref()[0].iter().any(|&value| value >= 300));
+
+    let mut frontier = frontier_from_shards(shards.clone());
+    frontier.par_shuffle(&mut rng);
+    assert_ne!((0..1000).collect::<Vec<_>>(), frontier.concat());
+    for (vector, shard) in frontier.as_ref().iter().zip(shards) {
+        let mut vector = vector.clone();
+        vector.sort_unstable();
+        assert_eq!(shard, vector);
+    }
+}
+
+#[test]
+fn test_count_if() {
+    let pool = ThreadPoolBuilder::default().num_threads(3).build().unwrap();
+    let frontier = Frontier::with_threads(&pool, None);
+    pool.install(|| (0..1001).into_par_iter().for_each(|i| frontier.push(i)));
+
+    assert_eq!(501, frontier.count_if(|value| value % 2 == 0));
+    assert_eq!(501, frontier.par_count_if(|value| value % 2 == 0));
+    assert_eq!(0, frontier.par_count_if(|value| *value > 1000));
+}
+
+#[test]
+fn test_windows() {
+    let frontier = frontier_from_shards(vec![vec![1, 2], vec![], vec![3], vec![4]]);
+
+    assert_eq!(
+        vec![vec![&1, &2], vec![&2, &3], vec![&3, &4]],
+        frontier.windows(2).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![vec![&1, &2, &3, &4]],
+        frontier.windows(4).collect::<Vec<_>>()
+    );
+    assert_eq!(0, frontier.windows(5).count());
+    assert_eq!(4, frontier.windows(1).count());
+}
+
+#[test]
+#[should_panic(expected = "window size must be non-zero")]
+fn test_windows_zero_size() {
+    let frontier = frontier_from_shards(vec![vec![1, 2]]);
+    let _ = frontier.windows(0);
+}
+
+#[test]
+fn test_push_balanced() {
+    let mut frontier = Frontier::with_index_fn(7, || 0);
+    for i in 0..1000 {
+        frontier.push_balanced(i);
+    }
+
+    let sizes = frontier.vector_sizes();
+    assert_eq!(vec![143, 143, 143, 143, 143, 143, 142], sizes);
+    assert_eq!((0..1000).collect::<Vec<_>>(), frontier.into_sorted_vec());
+}
+
+#[test]
+fn test_par_count_by() {
+    let frontier = frontier_from_shards(vec![vec![1, 2, 3, 4], vec![], vec![5, 6], vec![7]]);
+
+    assert_eq!(
+        vec![2, 0, 1, 0],
+        frontier.par_count_by(|value| value % 2 == 0)
+    );
+    assert_eq!(frontier.vector_sizes(), frontier.par_count_by(|_| true));
+}
+
+#[test]
+fn test_splice_shard() {
+    let mut frontier = frontier_from_shards(vec![vec![1, 2], vec![3, 4, 5, 6]]);
+
+    let removed = frontier
+        .splice_shard(1, 1..3, vec![7, 8, 9])
+        .collect::<Vec<_>>();
+
+    assert_eq!(vec![4, 5], removed);
+    assert_eq!(
+        vec![&3, &7, &8, &9, &6],
+        frontier.as_ref()[1].iter().collect::<Vec<_>>()
+    );
+    assert_eq!(vec![2, 5], frontier.vector_sizes());
+}
+
+#[test]
+#[should_panic(expected = "the range is 1..5 but the sub-vector 1 has 4 elements")]
+fn test_splice_shard_out_of_range() {
+    let mut frontier = frontier_from_shards(vec![vec![1, 2], vec![3, 4, 5, 6]]);
+    frontier.splice_shard(1, 1..5, vec![]);
+}
+
+#[test]
+fn test_try_from_shards_empty() {
+    let result = Frontier::<usize>::try_from_shards(vec![]);
+    assert_eq!(Some(FrontierError::NoShards), result.err());
+}
+
+#[test]
+fn test_try_from_shards() {
+    let frontier = Frontier::try_from_shards(vec![vec![1, 2], vec![], vec![3]]).unwrap();
+    assert_eq!(3, frontier.number_of_threads());
+    assert_eq!(vec![2, 0, 1], frontier.vector_sizes());
+    assert_eq!(vec![&1, &2, &3], frontier.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_rev_iter() {
+    let cases: Vec<Vec<Vec<i32>>> = vec![
+        // empty frontier
+        vec![vec![], vec![], vec![]],
+        // single element
+        vec![vec![1]],
+        vec![vec![], vec![1], vec![]],
+        // single sub-vector with multiple elements
+        vec![vec![1, 2, 3, 4]],
+        // multiple sub-vectors with empty ones interspersed
+        vec![
+            vec![],
+            vec![1, 2],
+            vec![],
+            vec![],
+            vec![3],
+            vec![4, 5, 6],
+            vec![],
+        ],
+        vec![vec![1], vec![2], vec![3], vec![4]],
+    ];
+    for shards in cases {
+        let frontier = frontier_from_shards(shards.clone());
+        let mut expected = frontier.iter().copied().collect::<Vec<_>>();
+        expected.reverse();
+
+        assert_eq!(expected, frontier.rev_iter().copied().collect::<Vec<_>>());
+        assert_eq!(expected, frontier.iter().rev().copied().collect::<Vec<_>>());
+        assert_eq!(
+            shards.concat().into_iter().rev().collect::<Vec<_>>(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_iter_front_meets_back() {
+    let frontier = frontier_from_shards(vec![vec![1, 2], vec![], vec![3], vec![4, 5, 6], vec![]]);
+    for front_steps in 0..=6 {
+        let mut iter = frontier.iter();
+        let front = (&mut iter).take(front_steps).copied().collect::<Vec<_>>();
+        let back = iter.rev().copied().collect::<Vec<_>>();
+
+        assert_eq!((1..=front_steps as i32).collect::<Vec<_>>(), front);
+        assert_eq!((front_steps as i32 + 1..=6).rev().collect::<Vec<_>>(), back);
+    }
+
+    // alternating between the two ends until they meet
+    let mut iter = frontier.iter();
+    let mut alternated = Vec::new();
+    while let Some(value) = iter.next() {
+        alternated.push(*value);
+        if let Some(value) = iter.next_back() {
+            alternated.push(*value);
+        }
+    }
+    assert_eq!(vec![1, 6, 2, 5, 3, 4], alternated);
+    assert_eq!(None, iter.next_back());
+}
+
+#[test]
+fn test_constructors_match_builder() {
+    let pool = ThreadPoolBuilder::default().num_threads(3).build().unwrap();
+
+    let frontier = Frontier::<usize>::with_threads(&pool, Some(9));
+    let built = Frontier::<usize>::builder()
+        .threads(&pool)
+        .capacity(9)
+        .build();
+    assert_eq!(built.number_of_threads(), frontier.number_of_threads());
+    assert_eq!(built.shard_capacities(), frontier.shard_capacities());
+    assert!(std::ptr::eq(&pool, frontier.thread_pool().unwrap()));
+
+    let frontier = Frontier::<usize>::with_capacity(10);
+    let built = Frontier::<usize>::builder().capacity(10).build();
+    assert_eq!(built.number_of_threads(), frontier.number_of_threads());
+    assert_eq!(built.shard_capacities(), frontier.shard_capacities());
+    assert!(frontier.thread_pool().is_none());
+}
+
+#[test]
+fn test_par_iter_vectors_with_offset() {
+    let frontier = frontier_from_shards(vec![vec![5, 1], vec![], vec![2, 8], vec![3, 9, 8]]);
+
+    let offsets = frontier
+        .par_iter_vectors_with_offset()
+        .map(|(offset, _)| offset)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![0, 2, 2, 4], offsets);
+
+    let mut pairs = frontier
+        .par_iter_vectors_with_offset()
+        .flat_map_iter(|(offset, vector)| {
+            vector
+                .iter()
+                .enumerate()
+                .map(move |(i, value)| (offset + i, value))
+        })
+        .collect::<Vec<_>>();
+    pairs.sort();
+    assert_eq!(frontier.iter().enumerate().collect::<Vec<_>>(), pairs);
+}
+
+#[test]
+fn test_with_num_shards() {
+    // more sub-vectors than threads, i.e. oversubscription
+    let pool = ThreadPoolBuilder::default().num_threads(2).build().unwrap();
+    let frontier = Frontier::with_num_shards(8);
+    assert_eq!(8, frontier.number_of_threads());
+
+    pool.install(|| {
+        (0..10_000)
+            .into_par_iter()
+            .with_max_len(16)
+            .for_each(|i| frontier.push(i))
+    });
+
+    assert_eq!(8, frontier.number_of_threads());
+    assert_eq!((0..10_000).collect::<Vec<_>>(), frontier.to_sorted_vec());
+    assert!(frontier.vector_sizes()[2..].iter().all(|&size| size == 0));
+
+    // outside of a pool the values are pushed onto the first sub-vector
+    let frontier = Frontier::with_num_shards(3);
+    frontier.push(1);
+    frontier.push(2);
+    assert_eq!(vec![2, 0, 0], frontier.vector_sizes());
+    assert_eq!(Some(2), frontier.pop());
+}
+
+#[test]
+#[should_panic(expected = "the current thread index is 7 but the frontier has 2 sub-vectors")]
+fn test_with_num_shards_fewer_than_threads() {
+    let pool = ThreadPoolBuilder::default().num_threads(8).build().unwrap();
+    let frontier = Frontier::with_num_shards(2);
+    pool.broadcast(|context| {
+        if context.index() == 7 {
+            frontier.push(context.index());
+        }
+    });
+}
+
+#[test]
+fn test_drain_into_vec() {
+    let mut frontier = frontier_from_shards(vec![
+        vec![5, 1],
+        Vec::with_capacity(10),
+        vec![2, 8],
+        vec![3, 9, 8],
+    ]);
+    let expected = frontier.iter().copied().collect::<Vec<_>>();
+    let capacities = frontier.shard_capacities();
+
+    let values = frontier.drain_into_vec();
+
+    assert_eq!(expected, values);
+    assert_eq!(values.len(), values.capacity());
+    assert!(frontier.is_empty());
+    assert_eq!(vec![0, 0, 0, 0], frontier.vector_sizes());
+    assert_eq!(capacities, frontier.shard_capacities());
+}
+
+#[test]
+#[should_panic(expected = "the current thread index is 3 but the frontier has 2 sub-vectors")]
+fn test_push_from_pool_with_more_threads() {
+    let pool = ThreadPoolBuilder::default().num_threads(4).build().unwrap();
+    let frontier = Frontier::builder().threads(&pool).num_shards(2).build();
+    pool.broadcast(|context| {
+        if context.index() == 3 {
+            frontier.push(context.index());
+        }
+    });
+}
+
+#[test]
+#[should_panic(expected = "the current thread index is 5 but the frontier has 2 sub-vectors")]
+fn test_push_index_fn_out_of_range() {
+    let frontier = Frontier::with_index_fn(2, || 5);
+    frontier.push(1);
+}
+
+#[test]
+fn test_retain_shards() {
+    let mut frontier = frontier_from_shards(vec![
+        vec![],
+        vec![1, 2],
+        vec![],
+        vec![],
+        vec![3],
+        vec![4, 5],
+        vec![],
+    ]);
+    let expected = frontier.iter().copied().collect::<Vec<_>>();
+
+    frontier.retain_shards();
+
+    assert_eq!(3, frontier.number_of_threads());
+    assert_eq!(vec![2, 1, 2], frontier.vector_sizes());
+    assert_eq!(expected, frontier.iter().copied().collect::<Vec<_>>());
+    assert_eq!(expected, frontier.par_iter().copied().collect::<Vec<_>>());
+
+    let mut frontier = frontier_from_shards::<i32>(vec![vec![], vec![], vec![]]);
+    frontier.retain_shards();
+    assert_eq!(1, frontier.number_of_threads());
+    assert!(frontier.is_empty());
+    assert_eq!(0, frontier.iter().count());
+}
+
+#[test]
+fn test_peek_then_pop() {
+    let mut frontier = frontier_from_shards(vec![vec![1, 2, 3], vec![], vec![4, 5]]);
+
+    while let Some(&value) = unsafe { frontier.peek() } {
+        assert_eq!(Some(value), frontier.pop());
+    }
+    assert_eq!(vec![0, 0, 2], frontier.vector_sizes());
+
+    for thread_id in 0..frontier.number_of_threads() {
+        while let Some(&value) = unsafe { frontier.peek_on_thread(thread_id) } {
+            assert_eq!(Some(value), frontier.pop_shard(thread_id));
+        }
+    }
+    assert!(frontier.is_empty());
+}
+
+#[test]
+fn test_par_min_max_by_key() {
+    let frontier = frontier_from_shards(vec![
+        vec![(3, 'a'), (1, 'b')],
+        vec![],
+        vec![(7, 'c'), (1, 'd')],
+        vec![(7, 'e'), (4, 'f')],
+    ]);
+
+    // ties resolve as in the sequential iterator
+    assert_eq!(Some(&(1, 'b')), frontier.par_min_by_key(|(key, _)| *key));
+    assert_eq!(Some(&(7, 'e')), frontier.par_max_by_key(|(key, _)| *key));
+    assert_eq!(
+        frontier.iter().min_by_key(|(key, _)| *key),
+        frontier.par_min_by_key(|(key, _)| *key)
+    );
+    assert_eq!(
+        frontier.iter().max_by_key(|(key, _)| *key),
+        frontier.par_max_by_key(|(key, _)| *key)
+    );
+
+    let frontier = frontier_from_shards::<i32>(vec![vec![], vec![]]);
+    assert_eq!(None, frontier.par_min_by_key(|value| *value));
+    assert_eq!(None, frontier.par_max_by_key(|value| *value));
+}
+
+#[test]
+fn test_dedup_by_key() {
+    let mut frontier = frontier_from_shards(vec![
+        vec![(1, 'a'), (1, 'b'), (2, 'c'), (2, 'd'), (1, 'e')],
+        vec![(1, 'f')],
+        vec![],
+        vec![(3, 'g'), (3, 'h')],
+    ]);
+
+    frontier.dedup_by_key(|(id, _)| *id);
+
+    assert_eq!(
+        vec![&(1, 'a'), &(2, 'c'), &(1, 'e'), &(1, 'f'), &(3, 'g')],
+        frontier.iter().collect::<Vec<_>>()
+    );
+    assert_eq!(vec![3, 1, 0, 1], frontier.vector_sizes());
+}
+
+#[test]
+fn test_shard_of() {
+    let frontier = frontier_from_shards(vec![
+        vec![],
+        vec![0, 1],
+        vec![],
+        vec![2],
+        vec![3, 4, 5],
+        vec![],
+    ]);
+
+    assert_eq!(Some((1, 0)), frontier.shard_of(0));
+    assert_eq!(Some((1, 1)), frontier.shard_of(1));
+    assert_eq!(Some((3, 0)), frontier.shard_of(2));
+    assert_eq!(Some((4, 0)), frontier.shard_of(3));
+    assert_eq!(Some((4, 2)), frontier.shard_of(5));
+    assert_eq!(None, frontier.shard_of(6));
+    assert_eq!(None, frontier.shard_of(usize::MAX));
+
+    for (global_index, value) in frontier.iter().enumerate() {
+        let (thread_id, offset) = frontier.shard_of(global_index).unwrap();
+        assert_eq!(value, &frontier.as_ref()[thread_id][offset]);
+    }
+
+    let frontier = frontier_from_shards::<i32>(vec![vec![], vec![]]);
+    assert_eq!(None, frontier.shard_of(0));
+}
+
+#[test]
+fn test_fill_shards_from() {
+    let mut frontier = Frontier::with_index_fn(4, || 0);
+    frontier.push(42);
+
+    frontier.fill_shards_from(|thread_id| vec![thread_id]);
+
+    assert_eq!(vec![1, 1, 1, 1], frontier.vector_sizes());
+    assert_eq!(
+        vec![0, 1, 2, 3],
+        frontier.iter().copied().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_merge_sorted() {
+    let left = frontier_from_shards(vec![vec![1, 4], vec![], vec![4, 9, 12]]);
+    let right = frontier_from_shards(vec![vec![0], vec![2, 4, 10], vec![11], vec![13, 20]]);
+    let mut expected = left.concat();
+    expected.extend(right.concat());
+    expected.sort();
+
+    let merged = left.merge_sorted(right);
+
+    assert_eq!(expected, merged.iter().copied().collect::<Vec<_>>());
+    // rebalanced over the sub-vectors of the first frontier
+    assert_eq!(vec![4, 4, 4], merged.vector_sizes());
+
+    let left = frontier_from_shards::<i32>(vec![vec![], vec![]]);
+    let right = frontier_from_shards(vec![vec![3], vec![5, 7]]);
+    let merged = left.merge_sorted(right);
+    assert_eq!(vec![3, 5, 7], merged.iter().copied().collect::<Vec<_>>());
+    assert_eq!(vec![2, 1], merged.vector_sizes());
+}
+
+#[test]
+fn test_heap_bytes() {
+    let frontier = frontier_from_shards(vec![
+        Vec::<u64>::with_capacity(10),
+        Vec::with_capacity(6),
+        Vec::new(),
+    ]);
+    let capacity = frontier.shard_capacities().into_iter().sum::<usize>();
+    assert!(capacity >= 16);
+
+    assert_eq!(
+        capacity * 8 + 3 * std::mem::size_of::<Vec<u64>>(),
+        frontier.heap_bytes()
+    );
+}
+
+#[test]
+fn test_heap_bytes_after_push_and_shrink() {
+    let frontier = Frontier::<u64>::with_index_fn(3, || 0);
+    let empty = frontier.heap_bytes();
+
+    for i in 0..1000 {
+        frontier.push(i);
+    }
+    let filled = frontier.heap_bytes();
+    assert!(filled >= empty + 1000 * 8);
+
+    let mut frontier = frontier;
+    frontier.pop();
+    frontier.reserve_shard(0, 10_000);
+    let reserved = frontier.heap_bytes();
+    assert!(reserved > filled);
+
+    frontier.shrink_to_fit();
+    assert!(frontier.heap_bytes() < reserved);
+    assert_eq!(
+        999 * 8 + 3 * std::mem::size_of::<Vec<u64>>(),
+        frontier.heap_bytes()
+    );
+}
+
+#[test]
+fn test_memory_footprint() {
+    let frontier = Frontier::<u64>::with_index_fn(2, || 0);
+    let empty = frontier.memory_footprint();
+    assert_eq!(frontier.heap_bytes(), empty);
+
+    (0..100).for_each(|i| frontier.push(i));
+    let filled = frontier.memory_footprint();
+    assert!(filled >= empty + 100 * 8);
+
+    let mut frontier = frontier;
+    frontier.truncate(10);
+    frontier.shrink_to_fit();
+    assert!(frontier.memory_footprint() < filled);
+    assert_eq!(frontier.heap_bytes(), frontier.memory_footprint());
+}
@@ -1,6 +1,10 @@
 extern crate parallel_frontier;
 use parallel_frontier::prelude::*;
-use rayon::{iter::plumbing::UnindexedProducer, ThreadPoolBuilder};
+use rayon::{
+    iter::plumbing::{Producer, UnindexedProducer},
+    ThreadPoolBuilder,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[test]
 fn test_par_iter() {
@@ -104,3 +108,450 @@ fn test_enumerate() {
     println!("{:?}", frontier.vector_sizes());
     assert_eq!(m * n, frontier.par_iter().enumerate().count());
 }
+
+#[test]
+fn test_par_iter_runs_on_thread_pool() {
+    let pool = ThreadPoolBuilder::default().num_threads(3).build().unwrap();
+    let frontier = Frontier::with_threads(&pool, None);
+
+    pool.install(|| {
+        (0..1000).into_par_iter().for_each(|i| {
+            frontier.push(i);
+        });
+    });
+
+    let visited = AtomicUsize::new(0);
+    frontier.par_iter().for_each(|_| {
+        assert_eq!(3, rayon::current_num_threads());
+        assert!(rayon::current_thread_index().is_some());
+        visited.fetch_add(1, Ordering::Relaxed);
+    });
+    assert_eq!(1000, visited.load(Ordering::Relaxed));
+
+    assert!(frontier
+        .par_iter()
+        .map(|_| rayon::current_num_threads())
+        .collect::<Vec<_>>()
+        .into_iter()
+        .all(|pool_size| pool_size == 3));
+}
+
+#[test]
+fn test_par_iter_adaptors_run_on_caller_pool() {
+    let pool = ThreadPoolBuilder::default().num_threads(3).build().unwrap();
+    let caller_pool = ThreadPoolBuilder::default().num_threads(2).build().unwrap();
+    let frontier = Frontier::with_threads(&pool, None);
+    pool.install(|| (0..1000).into_par_iter().for_each(|i| frontier.push(i)));
+
+    // the plain iterator is driven within the frontier pool
+    let pool_sizes = caller_pool.install(|| {
+        frontier
+            .par_iter()
+            .map(|_| rayon::current_num_threads())
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(vec![3; 1000], pool_sizes);
+
+    // while the indexed adaptors run in the pool of the caller
+    let pool_sizes = caller_pool.install(|| {
+        frontier
+            .par_iter()
+            .enumerate()
+            .rev()
+            .with_max_len(10)
+            .zip(frontier.par_iter())
+            .map(|_| rayon::current_num_threads())
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(vec![2; 1000], pool_sizes);
+
+    let pool_sizes = pool.install(|| {
+        frontier
+            .par_chunks(10)
+            .map(|_| rayon::current_num_threads())
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(vec![3; 100], pool_sizes);
+
+    let pool_sizes = caller_pool.install(|| {
+        frontier
+            .par_step_by(7)
+            .map(|_| rayon::current_num_threads())
+            .collect::<Vec<_>>()
+    });
+    assert!(pool_sizes.into_iter().all(|pool_size| pool_size == 2));
+}
+
+#[test]
+fn test_into_par_iter() {
+    let pool = ThreadPoolBuilder::default().num_threads(4).build().unwrap();
+    let frontier = Frontier::with_threads(&pool, None);
+    let values = (0..10_000).collect::<Vec<_>>();
+
+    pool.install(|| values.par_iter().for_each(|&i| frontier.push(i)));
+    let expected = frontier.concat();
+
+    let owned = pool.install(|| frontier.into_par_iter().collect::<Vec<_>>());
+    assert_eq!(expected, owned);
+
+    let mut sorted = owned;
+    sorted.sort();
+    assert_eq!(values, sorted);
+}
+
+#[test]
+fn test_into_par_iter_partial_consumption() {
+    /// Value counting how many times it was dropped.
+    #[derive(Clone)]
+    struct Tracked<'a>(usize, &'a AtomicUsize);
+
+    impl Drop for Tracked<'_> {
+        fn drop(&mut self) {
+            self.1.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let pool = ThreadPoolBuilder::default().num_threads(4).build().unwrap();
+    let drops = AtomicUsize::new(0);
+    let frontier = Frontier::with_threads(&pool, None);
+
+    pool.install(|| {
+        (0..1000)
+            .into_par_iter()
+            .for_each(|i| frontier.push(Tracked(i, &drops)))
+    });
+    let expected = frontier.iter().map(|value| value.0).collect::<Vec<_>>();
+
+    let last = pool.install(|| {
+        frontier
+            .clone()
+            .into_par_iter()
+            .rev()
+            .take(10)
+            .map(|value| value.0)
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(
+        expected.iter().rev().take(10).copied().collect::<Vec<_>>(),
+        last
+    );
+    assert_eq!(1000, drops.load(Ordering::Relaxed));
+
+    let found = pool.install(|| {
+        frontier
+            .into_par_iter()
+            .find_first(|value| value.0 == 500)
+            .map(|value| value.0)
+    });
+    assert_eq!(Some(500), found);
+    assert_eq!(2000, drops.load(Ordering::Relaxed));
+}
+
+#[test]
+fn test_par_iter_enumerate_indices() {
+    let pool = ThreadPoolBuilder::default().num_threads(4).build().unwrap();
+    let shards: Vec<Vec<usize>> = vec![
+        (0..7).collect(),
+        vec![],
+        (7..8).collect(),
+        vec![],
+        vec![],
+        (8..50).collect(),
+        (50..53).collect(),
+        vec![],
+    ];
+    let mut frontier = Frontier::with_index_fn(shards.len(), || 0);
+    frontier
+        .as_mut()
+        .iter_mut()
+        .zip(shards)
+        .for_each(|(vector, shard)| *vector = shard);
+
+    let mut enumerated = pool.install(|| {
+        frontier
+            .par_iter()
+            .with_max_len(1)
+            .enumerate()
+            .map(|(i, value)| (i, *value))
+            .collect::<Vec<_>>()
+    });
+    enumerated.sort_unstable();
+
+    assert_eq!(
+        frontier.iter().copied().enumerate().collect::<Vec<_>>(),
+        enumerated
+    );
+}
+
+#[test]
+fn test_concurrent_push_stress() {
+    let pool = ThreadPoolBuilder::default().num_threads(4).build().unwrap();
+    let (n, rounds) = if cfg!(miri) {
+        (1_000, 1)
+    } else {
+        (100_000, 10)
+    };
+
+    for _ in 0..rounds {
+        let frontier = Frontier::with_threads(&pool, None);
+        pool.install(|| {
+            (0..n)
+                .into_par_iter()
+                .with_max_len(64)
+                .for_each(|i| frontier.push(i))
+        });
+
+        assert_eq!(n, frontier.len());
+        assert_eq!((0..n).collect::<Vec<_>>(), frontier.into_sorted_vec());
+    }
+}
+
+#[test]
+fn test_par_iter_len() {
+    let frontier = Frontier::new();
+    (0..100).into_par_iter().for_each(|i| frontier.push(i));
+
+    let par_iter = frontier.par_iter();
+    assert_eq!(Some(frontier.len()), par_iter.opt_len());
+    assert_eq!(frontier.len(), par_iter.len());
+    assert_eq!(frontier.len(), frontier.par_iter().count());
+    assert_eq!(
+        frontier.iter().collect::<Vec<_>>(),
+        frontier.par_iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_par_iter_with_min_len() {
+    let pool = ThreadPoolBuilder::default().num_threads(4).build().unwrap();
+    let frontier = Frontier::with_threads(&pool, None);
+    let n = 100_000;
+    pool.install(|| (0..n).into_par_iter().for_each(|i| frontier.push(i)));
+
+    let count_tasks = |min_len: usize| {
+        let tasks = AtomicUsize::new(0);
+        let sum = AtomicUsize::new(0);
+        // the maximum length forces splitting as much as the minimum allows
+        frontier
+            .par_iter_with_min_len(min_len)
+            .with_max_len(1)
+            .for_each_init(
+                || tasks.fetch_add(1, Ordering::Relaxed),
+                |_, value| {
+                    sum.fetch_add(*value, Ordering::Relaxed);
+                },
+            );
+        assert_eq!((0..n).sum::<usize>(), sum.into_inner());
+        tasks.into_inner()
+    };
+
+    let small_tasks = count_tasks(1);
+    let large_tasks = count_tasks(1024);
+    assert_eq!(n, small_tasks);
+    assert!(large_tasks <= n / 1024);
+}
+
+#[test]
+fn test_par_iter_with_min_len_both_drive_paths() {
+    let mut frontier = Frontier::with_index_fn(4, || 0);
+    let n = 10_000;
+    frontier
+        .as_mut()
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, vector)| vector.extend(i * n / 4..(i + 1) * n / 4));
+    let pool = ThreadPoolBuilder::default().num_threads(4).build().unwrap();
+
+    let min_len = 500;
+    // without indexed adaptors, `for_each_init` drives the iterator through `drive_unindexed`
+    let unindexed_tasks = AtomicUsize::new(0);
+    pool.install(|| {
+        frontier.par_iter_with_min_len(min_len).for_each_init(
+            || unindexed_tasks.fetch_add(1, Ordering::Relaxed),
+            |_, _| {},
+        )
+    });
+    assert!(unindexed_tasks.into_inner() <= n / min_len);
+
+    // `enumerate` and `with_max_len` drive the iterator through `with_producer`,
+    // and the maximum length forces splitting as much as the minimum allows
+    let indexed_tasks = AtomicUsize::new(0);
+    let indices = pool.install(|| {
+        frontier
+            .par_iter_with_min_len(min_len)
+            .enumerate()
+            .with_max_len(1)
+            .map_init(
+                || indexed_tasks.fetch_add(1, Ordering::Relaxed),
+                |_, (i, _)| i,
+            )
+            .collect::<Vec<_>>()
+    });
+    assert_eq!((0..n).collect::<Vec<_>>(), indices);
+    assert!(indexed_tasks.into_inner() <= n / min_len);
+
+    // the unindexed producer, split through `bridge_unindexed`, stops at
+    // the minimum too, while the adaptive splitter alone would split further
+    let min_len = 3000;
+    let parts = pool.install(|| {
+        rayon::iter::split(
+            frontier.iter().with_min_len(min_len),
+            UnindexedProducer::split,
+        )
+        .map(|part| part.len())
+        .collect::<Vec<_>>()
+    });
+    assert_eq!(n, parts.iter().sum::<usize>());
+    assert!(parts.len() > 1);
+    assert!(parts.iter().all(|&len| len >= min_len));
+}
+
+#[test]
+fn test_into_par_iter_owned_sum() {
+    let pool = ThreadPoolBuilder::default().num_threads(3).build().unwrap();
+    let frontier = Frontier::with_threads(&pool, None);
+    pool.install(|| {
+        (0..10_000_u64)
+            .into_par_iter()
+            .for_each(|i| frontier.push(i))
+    });
+    let expected = frontier.concat();
+
+    let sum: u64 = pool.install(|| frontier.clone().into_par_iter().sum());
+    assert_eq!((0..10_000_u64).sum::<u64>(), sum);
+
+    let mut owned = Vec::new();
+    frontier.into_par_iter().collect_into_vec(&mut owned);
+    assert_eq!(expected, owned);
+}
+
+#[test]
+fn test_par_step_by() {
+    let pool = ThreadPoolBuilder::default().num_threads(4).build().unwrap();
+    let frontier = Frontier::with_threads(&pool, None);
+    pool.install(|| (0..1000).into_par_iter().for_each(|i| frontier.push(i)));
+
+    for step in [1, 2, 3, 7, 999, 1000, 2000] {
+        assert_eq!(
+            frontier.iter().step_by(step).collect::<Vec<_>>(),
+            frontier
+                .par_step_by(step)
+                .with_max_len(1)
+                .collect::<Vec<_>>(),
+            "step: {}",
+            step
+        );
+    }
+}
+
+#[test]
+fn test_par_iter_rev() {
+    let pool = ThreadPoolBuilder::default().num_threads(4).build().unwrap();
+    let shapes = vec![
+        vec![vec![], vec![], vec![]],
+        vec![vec![0]],
+        vec![(0..100).collect::<Vec<_>>()],
+        vec![
+            vec![],
+            (0..10).collect(),
+            vec![],
+            vec![],
+            (10..13).collect(),
+            vec![],
+        ],
+        (0..8).map(|i| (i * 10..i * 10 + i).collect()).collect(),
+    ];
+    for shards in shapes {
+        let mut frontier = Frontier::with_index_fn(shards.len(), || 0);
+        frontier
+            .as_mut()
+            .iter_mut()
+            .zip(shards)
+            .for_each(|(vector, shard)| *vector = shard);
+        let expected = frontier.iter().rev().copied().collect::<Vec<_>>();
+
+        for max_len in [1, 2, 3, 1000] {
+            let reversed = pool.install(|| {
+                frontier
+                    .par_iter()
+                    .rev()
+                    .with_max_len(max_len)
+                    .copied()
+                    .collect::<Vec<_>>()
+            });
+            assert_eq!(expected, reversed, "max_len: {}", max_len);
+        }
+
+        // every split point yields two halves whose reversals concatenate back
+        for index in 0..=frontier.len() {
+            let (low, high) = Producer::split_at(frontier.iter(), index);
+            let mut reversed = high.rev().copied().collect::<Vec<_>>();
+            reversed.extend(low.rev().copied());
+            assert_eq!(expected, reversed, "index: {}", index);
+        }
+    }
+}
+
+#[test]
+fn test_as_parallel_is_indexed_unlike_par_bridge() {
+    let pool = ThreadPoolBuilder::default().num_threads(4).build().unwrap();
+    let frontier = Frontier::with_threads(&pool, None);
+    let n = 100_000;
+    pool.install(|| (0..n).into_par_iter().for_each(|i| frontier.push(i)));
+
+    // the producer knows its length, so rayon can split it by index
+    assert_eq!(Some(n), frontier.as_parallel().opt_len());
+    assert_eq!(None, frontier.iter().par_bridge().opt_len());
+
+    let tasks = AtomicUsize::new(0);
+    let sum = pool.install(|| {
+        frontier
+            .as_parallel()
+            .with_max_len(1024)
+            .map_init(|| tasks.fetch_add(1, Ordering::Relaxed), |_, value| *value)
+            .sum::<usize>()
+    });
+    assert_eq!((0..n).sum::<usize>(), sum);
+    // every task gets a contiguous run of at most 1024 elements
+    assert!(tasks.into_inner() >= n / 1024);
+
+    let sum = pool.install(|| frontier.iter().par_bridge().copied().sum::<usize>());
+    assert_eq!((0..n).sum::<usize>(), sum);
+
+    // unlike par_bridge, the order of the elements is preserved
+    assert_eq!(
+        frontier.iter().collect::<Vec<_>>(),
+        frontier.as_parallel().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_par_iter_try_for_each_short_circuits() {
+    let pool = ThreadPoolBuilder::default().num_threads(4).build().unwrap();
+    let frontier = Frontier::with_threads(&pool, None);
+    let n = 100_000;
+    pool.install(|| (0..n).into_par_iter().for_each(|i| frontier.push(i)));
+
+    let visited = AtomicUsize::new(0);
+    let result = frontier.par_iter().try_for_each(|&value| {
+        assert_eq!(4, rayon::current_num_threads());
+        visited.fetch_add(1, Ordering::Relaxed);
+        if value % 1000 == 999 {
+            Err(value)
+        } else {
+            Ok(())
+        }
+    });
+
+    assert_eq!(Some(999), result.err().map(|value| value % 1000));
+    assert!(visited.into_inner() < n / 2);
+
+    // without errors every element is visited
+    let visited = AtomicUsize::new(0);
+    let result: Result<(), usize> = frontier.par_iter().try_for_each(|_| {
+        visited.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    });
+    assert!(result.is_ok());
+    assert_eq!(n, visited.into_inner());
+}
@@ -0,0 +1,47 @@
+extern crate parallel_frontier;
+use parallel_frontier::prelude::*;
+use rayon::ThreadPoolBuilder;
+use std::collections::BinaryHeap;
+
+#[test]
+fn test_drain_sorted_iter() {
+    let pool = ThreadPoolBuilder::default().num_threads(3).build().unwrap();
+    let mut frontier = PriorityFrontier::with_threads(&pool);
+    let values = (0..1000).map(|i| (i * 7919) % 1009).collect::<Vec<usize>>();
+
+    pool.install(|| values.par_iter().for_each(|value| frontier.push(*value)));
+
+    assert_eq!(3, frontier.number_of_threads());
+    assert_eq!(values.len(), frontier.len());
+
+    let mut heap = values.into_iter().collect::<BinaryHeap<_>>();
+    let drained = frontier.drain_sorted_iter().collect::<Vec<_>>();
+    let expected = std::iter::from_fn(|| heap.pop()).collect::<Vec<_>>();
+
+    assert_eq!(expected, drained);
+    assert!(frontier.is_empty());
+}
+
+#[test]
+fn test_pop_local_max() {
+    let frontier = PriorityFrontier::new();
+    for value in [3, 1, 4, 1, 5, 9, 2, 6] {
+        frontier.push(value);
+    }
+
+    assert_eq!(Some(9), frontier.pop());
+    assert_eq!(Some(6), frontier.pop());
+    assert_eq!(6, frontier.len());
+}
+
+#[test]
+fn test_drain_sorted_iter_dropped_early() {
+    let mut frontier = PriorityFrontier::new();
+    (0..10).for_each(|value| frontier.push(value));
+
+    assert_eq!(
+        vec![9, 8],
+        frontier.drain_sorted_iter().take(2).collect::<Vec<_>>()
+    );
+    assert!(frontier.is_empty());
+}
@@ -0,0 +1,54 @@
+extern crate parallel_frontier;
+use parallel_frontier::prelude::*;
+
+#[test]
+fn test_thread_local_frontier() {
+    let n_workers = 4;
+    let n = 1000;
+    let mut frontier = ThreadLocalFrontier::new();
+
+    std::thread::scope(|scope| {
+        for _ in 0..n_workers {
+            let frontier = &frontier;
+            scope.spawn(move || {
+                let index = frontier.register();
+                assert_eq!(index, frontier.register());
+                for i in 0..n {
+                    frontier.push(i);
+                }
+            });
+        }
+    });
+
+    assert_eq!(n_workers, frontier.number_of_threads());
+    assert_eq!(n_workers * n, frontier.len());
+    let mut values = frontier.concat();
+    values.sort_unstable();
+    assert_eq!(
+        (0..n)
+            .flat_map(|i| std::iter::repeat_n(i, n_workers))
+            .collect::<Vec<_>>(),
+        values
+    );
+}
+
+#[test]
+fn test_thread_local_frontier_independent_registrations() {
+    let mut first = ThreadLocalFrontier::new();
+    let mut second = ThreadLocalFrontier::new();
+    first.register();
+    first.push(1);
+
+    assert_eq!(0, second.number_of_threads());
+    second.register();
+    second.push(2);
+    assert_eq!(vec![1], first.concat());
+    assert_eq!(vec![2], second.concat());
+}
+
+#[test]
+#[should_panic(expected = "the current thread is not registered to this frontier")]
+fn test_thread_local_frontier_unregistered() {
+    let frontier = ThreadLocalFrontier::new();
+    frontier.push(0);
+}